@@ -1,5 +1,4 @@
-use std::collections::HashMap;
-
+use common::numbers::digits::{digit_runs, digits};
 use common::problem::day::{AoCProblem, Solution};
 
 pub struct Day04 {
@@ -18,72 +17,19 @@ impl AoCProblem for Day04 {
     }
 
     fn part1(&mut self) -> Solution {
-        let mut valid_passwords = 0;
-
-        'main: for mut num in self.min..=self.max {
-            let mut digits = vec![];
-            while num > 0 {
-                digits.insert(0, num % 10);
-                num /= 10;
-            }
-
+        (self.min..=self.max)
             // Two adjacent digits are the same
-            if digits.windows(2).all(|x| x[0] != x[1]) {
-                continue;
-            }
-
-            // Going from left to right, the digits never decrease
-            for window in digits.windows(2) {
-                if window[0] <= window[1] {
-                    continue;
-                }
-
-                continue 'main;
-            }
-
-            valid_passwords += 1;
-        }
-
-        valid_passwords.into()
+            .filter(|&num| is_valid_password(num, |len| len >= 2))
+            .count()
+            .into()
     }
 
     fn part2(&mut self) -> Solution {
-        let mut valid_passwords = 0;
-
-        'main: for mut num in self.min..=self.max {
-            let mut digits = vec![];
-            while num > 0 {
-                digits.insert(0, num % 10);
-                num /= 10;
-            }
-
-            // Two adjacent digits are the same
-            let mut map = HashMap::new();
-            digits.windows(2).for_each(|w| {
-                if w[0] != w[1] {
-                    return;
-                }
-
-                *map.entry(w[0]).or_insert(0) += 1;
-            });
-
-            if map.iter().all(|(_, v)| *v != 1) {
-                continue;
-            }
-
-            // Going from left to right, the digits never decrease
-            for window in digits.windows(2) {
-                if window[0] <= window[1] {
-                    continue;
-                }
-
-                continue 'main;
-            }
-
-            valid_passwords += 1;
-        }
-
-        valid_passwords.into()
+        (self.min..=self.max)
+            // Two adjacent digits are the same, but aren't part of a larger group
+            .filter(|&num| is_valid_password(num, |len| len == 2))
+            .count()
+            .into()
     }
 
     fn day() -> u32 {
@@ -94,3 +40,17 @@ impl AoCProblem for Day04 {
         2019
     }
 }
+
+/// Checks if the given number is a valid password.
+///
+/// # Parameters
+/// - `num`: The number to check.
+/// - `valid_run`: Whether a run of the same digit with the given length satisfies the
+///   adjacent digits rule.
+///
+/// # Returns
+/// `true` if going from left to right, the digits never decrease and some run of the same
+/// digit is valid, `false` otherwise.
+fn is_valid_password(num: usize, valid_run: impl Fn(usize) -> bool) -> bool {
+    digits(num, 10).is_sorted() && digit_runs(num, 10).any(|(_, len)| valid_run(len))
+}
@@ -1,5 +1,6 @@
 use common::{
-    intcode::{get_digits, parse_intcode, IntCodeComputer},
+    intcode::{parse_intcode, IntCodeComputer, Network, Routing},
+    numbers::digits::digits,
     problem::day::{AoCProblem, Solution},
 };
 
@@ -65,14 +66,6 @@ impl AoCProblem for Day07 {
     }
 
     fn part2(&mut self) -> Solution {
-        let mut computers: [IntCodeComputer; 5] = [
-            IntCodeComputer::new(&self.program, None),
-            IntCodeComputer::new(&self.program, None),
-            IntCodeComputer::new(&self.program, None),
-            IntCodeComputer::new(&self.program, None),
-            IntCodeComputer::new(&self.program, None),
-        ];
-
         let mut highest: isize = 0;
 
         // Is it brute-force time?
@@ -81,47 +74,35 @@ impl AoCProblem for Day07 {
                 for c in 5..=9 {
                     for d in 5..=9 {
                         for e in 5..=9 {
-                            computers.iter_mut().for_each(|x| x.reset());
                             let digits =
                                 match valid_setting(e + d * 10 + c * 100 + b * 1000 + a * 10000) {
                                     Some(d) => d,
                                     None => continue,
                                 };
 
-                            // Load initial digits to all computers
-                            for (i, c) in computers.iter_mut().enumerate() {
-                                c.input_to_stdin(digits[i]);
-                            }
-
-                            // Initially, run the very first computer
-                            computers[0].input_to_stdin(0);
-                            computers[0].run_until_output();
-
-                            let mut output = *computers[0]
+                            // Load initial digits to all computers, and connect each amplifier
+                            // to the next one (with the last one feeding back into the first).
+                            let computers = digits
+                                .iter()
+                                .map(|digit| {
+                                    IntCodeComputer::new(&self.program, Some(vec![*digit]))
+                                })
+                                .collect();
+                            let mut network = Network::new(
+                                computers,
+                                Routing::Pipes(vec![vec![1], vec![2], vec![3], vec![4], vec![0]]),
+                            );
+
+                            network.machine_mut(0).input_to_stdin(0);
+                            network
+                                .run_until_idle()
+                                .expect("amplifier program should be valid");
+
+                            let res = network
+                                .machine(4)
                                 .view_stdout()
                                 .last()
-                                .expect("no stdout on init");
-                            let mut curr_idx = 1;
-
-                            loop {
-                                if computers[4].has_halted() {
-                                    break;
-                                }
-
-                                computers[curr_idx].input_to_stdin(output);
-                                computers[curr_idx].run_until_output();
-
-                                output = match computers[curr_idx].view_stdout().last() {
-                                    Some(o) => *o,
-                                    // This shouldn't hit
-                                    None => break,
-                                };
-
-                                curr_idx += 1;
-                                curr_idx %= computers.len();
-                            }
-
-                            let res = computers[4].view_stdout().last().expect("no final stdout");
+                                .expect("no final stdout");
                             highest = max(*res, highest);
                         }
                     }
@@ -149,16 +130,14 @@ impl AoCProblem for Day07 {
 /// # Returns
 /// A vector of digits if the number is valid, or `None` if it is not.
 fn valid_setting(num: isize) -> Option<Vec<isize>> {
-    let mut digits = get_digits(num);
-    digits.reverse();
-    while digits.len() < 5 {
-        digits.push(0);
+    let mut setting = vec![0; 5];
+    for (slot, digit) in setting.iter_mut().rev().zip(digits(num, 10).rev()) {
+        *slot = digit;
     }
-    digits.reverse();
 
     let mut mask = 0;
     // Make sure there are unique digits
-    for digit in &digits {
+    for digit in &setting {
         if mask & (1 << *digit) != 0 {
             return None;
         }
@@ -166,5 +145,5 @@ fn valid_setting(num: isize) -> Option<Vec<isize>> {
         mask |= 1 << *digit;
     }
 
-    Some(digits)
+    Some(setting)
 }
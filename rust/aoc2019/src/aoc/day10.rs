@@ -1,6 +1,9 @@
 use std::{cmp::max, collections::HashSet};
 
-use common::problem::day::{AoCProblem, Solution};
+use common::{
+    numbers::gcd,
+    problem::day::{AoCProblem, Solution},
+};
 
 pub struct Day10 {
     map: Vec<Vec<char>>,
@@ -181,27 +184,6 @@ fn count_direct_line_of_sight(map: &[Vec<char>], h: usize, w: usize) -> usize {
     c
 }
 
-/// Finds the GCD of the absolute values of `n` and `m`.
-///
-/// # Parameters
-/// - `n`: the first number.
-/// - `m`: the second number.
-///
-/// # Returns
-/// `gcd(|n|, |m|)`.
-fn gcd(n: isize, m: isize) -> isize {
-    let mut t_n = n.abs();
-    let mut t_m = m.abs();
-    while t_m != 0 {
-        if t_m < t_n {
-            std::mem::swap(&mut t_m, &mut t_n);
-        }
-        t_m %= t_n;
-    }
-
-    t_n
-}
-
 /// Simplifies the given fraction, represented as `numerator/denominator`.
 ///
 /// # Parameters
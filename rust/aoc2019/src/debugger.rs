@@ -0,0 +1,328 @@
+use common::intcode::{
+    disassemble, disassemble_window, opcode_for_mnemonic, IntCodeComputer, Line, RunState,
+};
+use std::io::{self, BufRead, Write};
+
+/// The most values that a line of a listing holds.
+const VALUES_PER_LINE: usize = 8;
+
+const HELP: &str = "\
+Commands:
+  s, step [n]          Executes the next n instructions (default 1).
+  c, continue          Runs until the program halts, needs input, or hits a breakpoint.
+  t, trace             Toggles printing each instruction while continuing.
+  j, jump <addr>       Moves the instruction pointer to the address.
+  i, input <v>...      Adds the values to the input queue.
+  b, break <addr>      Stops before the instruction at the address.
+  bo <op>              Stops before any instruction with the mnemonic or opcode.
+  w, watch <addr>      Stops after any write to the address.
+  d, delete <addr>     Removes the breakpoint and watchpoint at the address.
+  do <op>              Removes the breakpoint for the mnemonic or opcode.
+  r, regs              Shows the instruction pointer, relative base and next instruction.
+  m, mem <addr> [n]    Shows n values of memory starting at the address (default 10).
+  l, list [addr] [n]   Disassembles n lines starting at the address (default: next 10).
+  o, out               Shows the output so far.
+  save <file>          Saves the state of the program to the file.
+  load <file>          Restores the state of the program from the file.
+  h, help              Shows this message.
+  q, quit              Exits the debugger.";
+
+/// Runs an interactive debugger for the given Intcode program, reading commands from the
+/// standard input.
+///
+/// # Parameters
+/// - `program`: The program.
+pub fn run_debugger(program: &[isize]) {
+    let mut computer = IntCodeComputer::new(program, None);
+    let mut tracing = false;
+    println!("Loaded {} values. Type \"h\" for help.", program.len());
+    print_registers(&computer);
+
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+
+        let args = words.collect::<Vec<_>>();
+        match command {
+            "s" | "step" => {
+                let Some(count) = parse_arg(args.first(), Some(1)) else {
+                    continue;
+                };
+
+                for _ in 0..count {
+                    print_instruction(&computer);
+                    match computer.try_step() {
+                        Ok(None) => {}
+                        Ok(Some(state)) => {
+                            print_state(state);
+                            if !matches!(state, RunState::Output(_) | RunState::Watchpoint { .. }) {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("[Error] {e}");
+                            break;
+                        }
+                    }
+                }
+            }
+            "c" | "continue" => loop {
+                let result = if tracing {
+                    computer.try_run_with_trace(|_, instruction| println!("  {instruction}"))
+                } else {
+                    computer.try_run()
+                };
+
+                match result {
+                    Ok(state) => {
+                        print_state(state);
+                        if !matches!(state, RunState::Output(_)) {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("[Error] {e}");
+                        break;
+                    }
+                }
+            },
+            "t" | "trace" => {
+                tracing = !tracing;
+                println!("Tracing is {}.", if tracing { "on" } else { "off" });
+            }
+            "i" | "input" => {
+                let values = args
+                    .iter()
+                    .map(|v| v.parse::<isize>())
+                    .collect::<Result<Vec<_>, _>>();
+                match values {
+                    Ok(values) => values.into_iter().for_each(|v| computer.input_to_stdin(v)),
+                    Err(_) => eprintln!("[Error] Inputs must be integers."),
+                }
+            }
+            "j" | "jump" => {
+                if let Some(address) = parse_arg(args.first(), None) {
+                    computer.set_instruction_pointer(address);
+                    print_registers(&computer);
+                }
+            }
+            "b" | "break" => {
+                if let Some(address) = parse_arg(args.first(), None) {
+                    computer.add_breakpoint(address);
+                }
+            }
+            "bo" => {
+                if let Some(opcode) = parse_opcode(args.first()) {
+                    computer.add_opcode_breakpoint(opcode);
+                }
+            }
+            "w" | "watch" => {
+                if let Some(address) = parse_arg(args.first(), None) {
+                    computer.add_watchpoint(address);
+                }
+            }
+            "d" | "delete" => {
+                if let Some(address) = parse_arg(args.first(), None) {
+                    let removed =
+                        computer.remove_breakpoint(address) | computer.remove_watchpoint(address);
+                    if !removed {
+                        println!("Nothing to remove at {address}.");
+                    }
+                }
+            }
+            "do" => {
+                if let Some(opcode) = parse_opcode(args.first()) {
+                    if !computer.remove_opcode_breakpoint(opcode) {
+                        println!("No breakpoint for opcode {opcode}.");
+                    }
+                }
+            }
+            "r" | "regs" => print_registers(&computer),
+            "m" | "mem" => {
+                let (Some(start), Some(count)) = (
+                    parse_arg(args.first(), Some(0)),
+                    parse_arg(args.get(1), Some(10)),
+                ) else {
+                    continue;
+                };
+
+                for address in start..start.saturating_add(count) {
+                    let value = if address < computer.memory_len() {
+                        computer[address]
+                    } else {
+                        0
+                    };
+
+                    println!("{address:04}: {value}");
+                }
+            }
+            "l" | "list" => {
+                let (Some(start), Some(count)) = (
+                    parse_arg(args.first(), Some(computer.instruction_pointer())),
+                    parse_arg(args.get(1), Some(10)),
+                ) else {
+                    continue;
+                };
+
+                // The program can write to very high addresses, so only copy as much memory as
+                // the listing needs.
+                let len = count.saturating_mul(VALUES_PER_LINE);
+                let lines = if start < program.len() {
+                    // The original program is disassembled as a whole, following its control
+                    // flow from address 0.
+                    let end = program.len().max(start.saturating_add(len));
+                    disassemble(&computer.view_memory(0, end.min(computer.memory_len())))
+                } else {
+                    let len = len.min(computer.memory_len().saturating_sub(start));
+                    disassemble_window(&computer.view_memory(start, len), start)
+                };
+
+                lines
+                    .into_iter()
+                    .filter(|line| line_end(line) > start)
+                    .take(count)
+                    .for_each(|line| println!("{line}"));
+            }
+            "o" | "out" => println!("{:?}", computer.view_stdout()),
+            "save" | "load" => {
+                let Some(path) = args.first() else {
+                    eprintln!("[Error] Missing argument.");
+                    continue;
+                };
+
+                let result = if command == "save" {
+                    computer.save_state(path)
+                } else {
+                    computer.load_state(path)
+                };
+
+                match result {
+                    Ok(()) if command == "save" => println!("Saved to {path}."),
+                    Ok(()) => print_registers(&computer),
+                    Err(e) => eprintln!("[Error] {e}"),
+                }
+            }
+            "h" | "help" => println!("{HELP}"),
+            "q" | "quit" => break,
+            _ => println!("Unknown command \"{command}\". Type \"h\" for help."),
+        }
+    }
+}
+
+/// Parses an optional numeric argument.
+///
+/// # Parameters
+/// - `arg`: The argument, if given.
+/// - `default`: The value to use if the argument wasn't given, or `None` if the argument is
+///   required.
+///
+/// # Returns
+/// The value, or `None` if the argument is invalid or missing (in which case an error is
+/// printed).
+fn parse_arg(arg: Option<&&str>, default: Option<usize>) -> Option<usize> {
+    match arg {
+        Some(arg) => arg
+            .parse::<usize>()
+            .inspect_err(|_| eprintln!("[Error] \"{arg}\" is not a valid number."))
+            .ok(),
+        None => {
+            if default.is_none() {
+                eprintln!("[Error] Missing argument.");
+            }
+
+            default
+        }
+    }
+}
+
+/// Parses an opcode given as either a mnemonic or a number.
+///
+/// # Parameters
+/// - `arg`: The argument, if given.
+///
+/// # Returns
+/// The opcode, or `None` if the argument is invalid or missing (in which case an error is
+/// printed).
+fn parse_opcode(arg: Option<&&str>) -> Option<isize> {
+    let Some(arg) = arg else {
+        eprintln!("[Error] Missing argument.");
+        return None;
+    };
+
+    let opcode = opcode_for_mnemonic(arg).or_else(|| arg.parse::<isize>().ok());
+    if opcode.is_none() {
+        eprintln!("[Error] \"{arg}\" is not a valid mnemonic or opcode.");
+    }
+
+    opcode
+}
+
+/// Gets the address right after a line of a listing.
+///
+/// # Parameters
+/// - `line`: The line.
+///
+/// # Returns
+/// The address after the last value in the line.
+fn line_end(line: &Line) -> usize {
+    match line {
+        Line::Instruction { instruction, .. } => instruction.next_address(),
+        Line::Data { address, values } => address + values.len(),
+    }
+}
+
+/// Prints the instruction at the instruction pointer.
+///
+/// # Parameters
+/// - `computer`: The computer.
+fn print_instruction(computer: &IntCodeComputer) {
+    if computer.instruction_pointer() >= computer.memory_len() {
+        println!("{:04}: <end of program>", computer.instruction_pointer());
+        return;
+    }
+
+    match computer.current_instruction() {
+        Ok(instruction) => println!("{instruction}"),
+        Err(e) => println!("{:04}: <{e}>", computer.instruction_pointer()),
+    }
+}
+
+/// Prints the registers of the computer and the instruction that it'll execute next.
+///
+/// # Parameters
+/// - `computer`: The computer.
+fn print_registers(computer: &IntCodeComputer) {
+    println!(
+        "ip={} rb={} halted={}",
+        computer.instruction_pointer(),
+        computer.relative_base(),
+        computer.has_halted()
+    );
+    print_instruction(computer);
+}
+
+/// Prints why the computer stopped.
+///
+/// # Parameters
+/// - `state`: Why the computer stopped.
+fn print_state(state: RunState) {
+    match state {
+        RunState::Halted => println!("Halted."),
+        RunState::NeedsInput => println!("Waiting for input; use \"i <value>\"."),
+        RunState::Output(v) => println!("Output: {v}"),
+        RunState::Breakpoint(address) => println!("Breakpoint at {address}."),
+        RunState::Watchpoint { address, old, new } => {
+            println!("Watchpoint: wrote {new} to [{address}] (was {old}).")
+        }
+    }
+}
@@ -1,6 +1,8 @@
-﻿use common::problem::run;
-use std::env;
+﻿use common::intcode::{disassemble, parse_intcode, IntCodeComputer};
+use common::problem::run;
+use std::{env, fs, io};
 mod aoc;
+mod debugger;
 
 fn main() {
     let args = env::args().skip(1).take(2).collect::<Vec<_>>();
@@ -8,6 +10,40 @@ fn main() {
         println!("Usage: ./aoc2019 <day> [test]");
         println!("\twhere <day> is an integer in [0, 25].");
         println!("\tand [test] is optionally a positive integer.");
+        println!("   or: ./aoc2019 disasm <file>");
+        println!("\twhere <file> contains an Intcode program to disassemble.");
+        println!("   or: ./aoc2019 debug <file>");
+        println!("\twhere <file> contains an Intcode program to debug.");
+        println!("   or: ./aoc2019 ascii <file>");
+        println!("\twhere <file> contains an ASCII Intcode program to run interactively.");
+        return;
+    }
+
+    if ["disasm", "debug", "ascii"].contains(&args[0].as_str()) {
+        let Some(path) = args.get(1) else {
+            println!("Usage: ./aoc2019 {} <file>", args[0]);
+            return;
+        };
+
+        let Some(program) = read_program(path) else {
+            return;
+        };
+
+        match args[0].as_str() {
+            "disasm" => {
+                for line in disassemble(&program) {
+                    println!("{line}");
+                }
+            }
+            "debug" => debugger::run_debugger(&program),
+            _ => {
+                let mut computer = IntCodeComputer::new(&program, None);
+                if let Err(e) = computer.run_interactive(io::stdin().lock(), io::stdout()) {
+                    eprintln!("[Error] {e}");
+                }
+            }
+        }
+
         return;
     }
 
@@ -43,3 +79,20 @@ fn main() {
         }
     }
 }
+
+/// Reads the Intcode program in the given file.
+///
+/// # Parameters
+/// - `path`: The path to the file containing the program.
+///
+/// # Returns
+/// The program, or `None` if the file couldn't be read (in which case an error is printed).
+fn read_program(path: &str) -> Option<Vec<isize>> {
+    match fs::read_to_string(path) {
+        Ok(code) => Some(parse_intcode(code.trim())),
+        Err(e) => {
+            eprintln!("[Error] Could not read \"{path}\": {e}");
+            None
+        }
+    }
+}
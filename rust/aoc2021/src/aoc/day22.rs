@@ -1,5 +1,5 @@
+use common::interval::{Cuboid, CuboidSet, IntInterval};
 use common::problem::day::{AoCProblem, Solution};
-use std::collections::HashSet;
 
 pub struct Day22 {
     reboot_steps: Vec<RebootStep>,
@@ -18,16 +18,13 @@ impl AoCProblem for Day22 {
                 .replace(",z=", " ")
                 .replace("..", " ");
 
-            let mut iterator = parsed_rest.split(' ').map(|x| x.parse::<i32>().unwrap());
+            let mut iterator = parsed_rest.split(' ').map(|x| x.parse::<isize>().unwrap());
+            let mut next_axis =
+                || IntInterval::new(iterator.next().unwrap(), iterator.next().unwrap());
 
             reboot_steps.push(RebootStep {
                 toggle: toggle_val == "on",
-                from_x: iterator.next().unwrap(),
-                to_x: iterator.next().unwrap(),
-                from_y: iterator.next().unwrap(),
-                to_y: iterator.next().unwrap(),
-                from_z: iterator.next().unwrap(),
-                to_z: iterator.next().unwrap(),
+                cuboid: Cuboid::new([next_axis(), next_axis(), next_axis()]),
             });
         }
 
@@ -35,37 +32,12 @@ impl AoCProblem for Day22 {
     }
 
     fn part1(&mut self) -> Solution {
-        let mut enabled: HashSet<(i32, i32, i32)> = HashSet::new();
-        for reboot_step in &self.reboot_steps {
-            if reboot_step.from_x < -50
-                || reboot_step.to_x > 50
-                || reboot_step.from_y < -50
-                || reboot_step.to_y > 50
-                || reboot_step.from_z < -50
-                || reboot_step.to_z > 50
-            {
-                continue;
-            }
-
-            for x in reboot_step.from_x..=reboot_step.to_x {
-                for y in reboot_step.from_y..=reboot_step.to_y {
-                    for z in reboot_step.from_z..=reboot_step.to_z {
-                        if reboot_step.toggle {
-                            enabled.insert((x, y, z));
-                            continue;
-                        }
-
-                        enabled.remove(&(x, y, z));
-                    }
-                }
-            }
-        }
-
-        enabled.len().into()
+        let region = Cuboid::new([IntInterval::new(-50, 50); 3]);
+        self.reboot().volume_within(&region).into()
     }
 
     fn part2(&mut self) -> Solution {
-        0.into()
+        self.reboot().volume().into()
     }
 
     fn day() -> u32 {
@@ -77,13 +49,27 @@ impl AoCProblem for Day22 {
     }
 }
 
+impl Day22 {
+    /// Runs every reboot step, turning the cubes in each step's cuboid on or off.
+    ///
+    /// # Returns
+    /// The cubes that are on after the last step.
+    fn reboot(&self) -> CuboidSet<3> {
+        let mut enabled = CuboidSet::new();
+        for reboot_step in &self.reboot_steps {
+            if reboot_step.toggle {
+                enabled.insert(reboot_step.cuboid);
+            } else {
+                enabled.remove(&reboot_step.cuboid);
+            }
+        }
+
+        enabled
+    }
+}
+
 #[derive(Copy, Clone)]
 struct RebootStep {
-    from_x: i32,
-    to_x: i32,
-    from_y: i32,
-    to_y: i32,
-    from_z: i32,
-    to_z: i32,
+    cuboid: Cuboid<3>,
     toggle: bool,
 }
@@ -1,5 +1,6 @@
 use common::{
     constants::TWO_NEWLINE,
+    numbers::lcm_all,
     problem::day::{AoCProblem, Solution},
 };
 
@@ -94,8 +95,9 @@ impl AoCProblem for Day11 {
 fn simulate_monkey_game(notes: &[Note], rounds: usize, div_worry_lvl_by: usize) -> usize {
     let mut num_inspections: Vec<usize> = vec![0; notes.len()];
     let mut monkeys: Vec<Vec<usize>> = notes.iter().map(|x| x.starting_items.clone()).collect();
-    // All of the numbers used when checking divisibility so happen to all be prime...
-    let main_mod = notes.iter().fold(1, |old, new| old * new.test_div);
+    // Only the worry level modulo each divisor matters, so the worry level can be kept modulo
+    // the LCM of the divisors without changing where any item is thrown.
+    let main_mod = lcm_all(notes.iter().map(|note| note.test_div));
 
     for _ in 0..rounds {
         for (idx, note) in notes.iter().enumerate() {
@@ -1,5 +1,6 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 
+use common::interval::{IntInterval, SegmentTree};
 use common::problem::day::{AoCProblem, Solution};
 
 // Lol, did someone say... *brute-force?*
@@ -100,52 +101,92 @@ impl AoCProblem for Day15 {
     }
 
     fn part2(&mut self) -> Solution {
-        let mut dict: HashMap<(isize, isize), usize> = HashMap::new();
-        const DIFF: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, 1), (1, -1)];
         const MAX_XY: isize = 4_000_000;
+        match self.find_distress_beacon(MAX_XY) {
+            Some((x, y)) => (x * 4000000 + y).into(),
+            None => 0.into(),
+        }
+    }
 
+    fn day() -> u32 {
+        15
+    }
+
+    fn year() -> u32 {
+        2022
+    }
+}
+
+impl Day15 {
+    /// Finds the only position, with both coordinates in `[0, max_xy]`, that no sensor covers.
+    ///
+    /// Rotating the grid by 45 degrees (`u = x + y`, `v = x - y`) turns each sensor's
+    /// diamond into a square. Sweeping over `u`, the squares' `v` ranges are added to and
+    /// removed from a segment tree that counts how many sensors cover each `v`. Coverage only
+    /// changes where a square starts or ends, so only those columns need to be searched for
+    /// an uncovered `v`.
+    ///
+    /// # Parameters
+    /// - `max_xy`: The largest possible coordinate.
+    ///
+    /// # Returns
+    /// The position, if there is one.
+    fn find_distress_beacon(&self, max_xy: isize) -> Option<(isize, isize)> {
+        let v_bounds = IntInterval::new(-max_xy, max_xy);
+        let mut events: BTreeMap<isize, Vec<(IntInterval, isize)>> = BTreeMap::new();
         for sensor in &self.sensors {
-            let mut x = sensor.x + sensor.manhattan_distance + 1;
-            let mut y = sensor.y;
-            for (dx, dy) in DIFF {
-                let mut remaining = sensor.manhattan_distance + 1;
-                while remaining > 0 {
-                    if (0..=MAX_XY).contains(&x) && (0..=MAX_XY).contains(&y) {
-                        *dict.entry((x, y)).or_insert(0) += 1;
-                    }
-                    x += dx;
-                    y += dy;
-                    remaining -= 1;
-                }
+            let (u, v, dist) = (
+                sensor.x + sensor.y,
+                sensor.x - sensor.y,
+                sensor.manhattan_distance,
+            );
+            let Some(v_range) = IntInterval::new(v - dist, v + dist).intersect(&v_bounds) else {
+                continue;
+            };
 
-                if (0..=MAX_XY).contains(&x) && (0..=MAX_XY).contains(&y) {
-                    *dict.entry((x, y)).or_insert(0) += 1;
-                }
-            }
+            events.entry(u - dist).or_default().push((v_range, 1));
+            events.entry(u + dist + 1).or_default().push((v_range, -1));
         }
 
-        let high_pts = dict
-            .into_iter()
-            .filter(|(_, v)| *v >= 4)
-            .collect::<Vec<_>>();
+        // The columns before the first square starts aren't covered at all, so make sure that
+        // the sweep searches them too.
+        events.entry(0).or_default();
 
-        for (pt, _) in &high_pts {
-            if self.sensors.iter().any(|x| x.is_in_sensor_range(*pt)) {
-                continue;
+        let mut coverage = SegmentTree::new(v_bounds);
+        let mut events = events.into_iter().peekable();
+        while let Some((u_start, changes)) = events.next() {
+            for (v_range, delta) in changes {
+                coverage.add(v_range, delta);
             }
 
-            return (pt.0 * 4000000 + pt.1).into();
-        }
+            // The coverage is the same for every u until the next event.
+            let u_start = u_start.max(0);
+            let u_end = events
+                .peek()
+                .map_or(2 * max_xy, |(u, _)| u - 1)
+                .min(2 * max_xy);
+            if u_start > u_end {
+                continue;
+            }
 
-        0.into()
-    }
+            // x = (u + v) / 2 and y = (u - v) / 2 are in [0, max_xy] iff
+            // |v| <= u <= 2 * max_xy - |v|.
+            let max_v = u_end.min(2 * max_xy - u_start).min(max_xy);
+            let mut v = -max_v;
+            while let Some(found) = coverage.first_at_most(IntInterval::new(v, max_v), 0) {
+                let low = u_start.max(found.abs());
+                let high = u_end.min(2 * max_xy - found.abs());
+                // u and v need the same parity for x and y to be integers.
+                let u = low + (low + found).rem_euclid(2);
+                if u <= high {
+                    return Some(((u + found) / 2, (u - found) / 2));
+                }
 
-    fn day() -> u32 {
-        15
-    }
+                v = found + 1;
+            }
+        }
 
-    fn year() -> u32 {
-        2022
+        None
     }
 }
 
@@ -181,3 +222,45 @@ impl From<&Sensor> for (isize, isize) {
         (b.closest_beacon_x, b.closest_beacon_y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day15;
+    use common::problem::day::AoCProblem;
+
+    #[test]
+    fn test_example() {
+        let day = Day15::prepare(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3"
+                .to_string(),
+        );
+        assert_eq!(Some((14, 11)), day.find_distress_beacon(20));
+    }
+
+    #[test]
+    fn test_beacon_at_origin() {
+        // Every position in [0, 4] x [0, 4] is covered except for (0, 0), which comes before
+        // any sensor's range starts in the sweep.
+        let day = Day15::prepare(
+            "Sensor at x=2, y=2: closest beacon is at x=2, y=5
+Sensor at x=4, y=0: closest beacon is at x=4, y=2
+Sensor at x=0, y=4: closest beacon is at x=0, y=2
+Sensor at x=4, y=4: closest beacon is at x=4, y=2"
+                .to_string(),
+        );
+        assert_eq!(Some((0, 0)), day.find_distress_beacon(4));
+    }
+}
@@ -1,21 +1,13 @@
-use std::cmp::min;
-
 use common::{
     constants::TWO_NEWLINE,
+    interval::{IntInterval, IntervalSet, RangeMap},
     problem::day::{AoCProblem, Solution},
 };
 
-type AlmanacEntry = [usize; 3];
-
 pub struct Day05 {
-    seeds: Vec<usize>,
-    seed_to_soil: Vec<AlmanacEntry>,
-    soil_to_fertilizer: Vec<AlmanacEntry>,
-    fertilizer_to_water: Vec<AlmanacEntry>,
-    water_to_light: Vec<AlmanacEntry>,
-    light_to_temp: Vec<AlmanacEntry>,
-    temp_to_humidity: Vec<AlmanacEntry>,
-    humidity_to_loc: Vec<AlmanacEntry>,
+    seeds: Vec<isize>,
+    /// The seed-to-soil, soil-to-fertilizer, ..., humidity-to-location maps, composed into one.
+    seed_to_loc: RangeMap,
 }
 
 impl AoCProblem for Day05 {
@@ -23,114 +15,48 @@ impl AoCProblem for Day05 {
         let split_input: Vec<&str> = input.split(TWO_NEWLINE).collect();
         // Parse seeds
         let (_, raw_seeds) = split_input[0].split_once(": ").unwrap();
-        let seeds: Vec<usize> = raw_seeds
+        let seeds: Vec<isize> = raw_seeds
             .split_whitespace()
-            .map(|seed| seed.parse::<usize>().unwrap())
+            .map(|seed| seed.parse::<isize>().unwrap())
             .collect();
 
-        let map_to_usize_vec = |s: &str| -> [usize; 3] {
-            let mut iter = s.split(' ').map(|m| m.parse::<usize>().unwrap());
-            [
+        // Each entry is "destination source length", which maps the source range to the
+        // destination range.
+        let parse_entry = |s: &str| -> (IntInterval, isize) {
+            let mut iter = s.split(' ').map(|m| m.parse::<isize>().unwrap());
+            let (dest, src, range) = (
                 iter.next().unwrap(),
                 iter.next().unwrap(),
                 iter.next().unwrap(),
-            ]
+            );
+            (IntInterval::new(src, src + range - 1), dest - src)
         };
 
-        let seed_to_soil: Vec<_> = split_input[1]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
-
-        let soil_to_fertilizer: Vec<_> = split_input[2]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
-
-        let fertilizer_to_water: Vec<_> = split_input[3]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
-
-        let water_to_light: Vec<_> = split_input[4]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
-
-        let light_to_temp: Vec<_> = split_input[5]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
-
-        let temp_to_humidity: Vec<_> = split_input[6]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
-
-        let humidity_to_loc: Vec<_> = split_input[7]
-            .lines()
-            .skip(1)
-            .map(map_to_usize_vec)
-            .collect();
+        let seed_to_loc = split_input[1..=7]
+            .iter()
+            .map(|map| map.lines().skip(1).map(parse_entry).collect::<RangeMap>())
+            .fold(RangeMap::new(), |composed, map| composed.then(&map));
 
-        Self {
-            seeds,
-            seed_to_soil,
-            soil_to_fertilizer,
-            fertilizer_to_water,
-            water_to_light,
-            light_to_temp,
-            temp_to_humidity,
-            humidity_to_loc,
-        }
+        Self { seeds, seed_to_loc }
     }
 
     fn part1(&mut self) -> Solution {
-        let mut lowest_location = usize::MAX;
-        for seed in &self.seeds {
-            let soil = almanac_mapper(&self.seed_to_soil, *seed);
-            let fertilizer = almanac_mapper(&self.soil_to_fertilizer, soil);
-            let water = almanac_mapper(&self.fertilizer_to_water, fertilizer);
-            let light = almanac_mapper(&self.water_to_light, water);
-            let temp = almanac_mapper(&self.light_to_temp, light);
-            let humidity = almanac_mapper(&self.temp_to_humidity, temp);
-            let location = almanac_mapper(&self.humidity_to_loc, humidity);
-            lowest_location = min(lowest_location, location);
-        }
-
-        lowest_location.into()
+        self.seeds
+            .iter()
+            .map(|seed| self.seed_to_loc.map_value(*seed))
+            .min()
+            .unwrap()
+            .into()
     }
 
     fn part2(&mut self) -> Solution {
-        // This is brute-force so I will probably come back here and fix it up later.
-        self.seeds
+        let seeds = self
+            .seeds
             .chunks(2)
-            .map(|chunk| {
-                let mut lowest = usize::MAX;
-                let seed_start = chunk[0];
-                let seed_end = seed_start + chunk[1] - 1;
-                for seed in seed_start..=seed_end {
-                    let soil = almanac_mapper(&self.seed_to_soil, seed);
-                    let fertilizer = almanac_mapper(&self.soil_to_fertilizer, soil);
-                    let water = almanac_mapper(&self.fertilizer_to_water, fertilizer);
-                    let light = almanac_mapper(&self.water_to_light, water);
-                    let temp = almanac_mapper(&self.light_to_temp, light);
-                    let humidity = almanac_mapper(&self.temp_to_humidity, temp);
-                    let location = almanac_mapper(&self.humidity_to_loc, humidity);
-                    lowest = min(lowest, location);
-                }
+            .map(|chunk| IntInterval::new(chunk[0], chunk[0] + chunk[1] - 1))
+            .collect::<IntervalSet>();
 
-                lowest
-            })
-            .min()
-            .unwrap()
-            .into()
+        self.seed_to_loc.map_set(&seeds).min().unwrap().into()
     }
 
     fn day() -> u32 {
@@ -141,24 +67,3 @@ impl AoCProblem for Day05 {
         2023
     }
 }
-
-/// Maps an input to its output according to the list of almanac entries.
-///
-/// # Parameters
-/// - `data`: The almanac.
-/// - `input`: The input item.
-///
-/// # Returns
-/// THe output based on the almanac.
-fn almanac_mapper(data: &[AlmanacEntry], input: usize) -> usize {
-    for [dest, src, range] in data {
-        let lower_src = *src;
-        let upper_src = *src + *range - 1;
-        if lower_src <= input && input <= upper_src {
-            return *dest + input - lower_src;
-        }
-    }
-
-    // Any source numbers that aren't mapped correspond to the same destination number.
-    input
-}
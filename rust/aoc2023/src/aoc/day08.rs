@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use common::{
     constants::TWO_NEWLINE,
-    numbers::lcm,
+    numbers::lcm_all,
     problem::day::{AoCProblem, Solution},
 };
 
@@ -112,7 +112,7 @@ impl AoCProblem for Day08 {
             instruction_idx %= self.instructions.len();
         }
 
-        num_steps.into_iter().reduce(lcm).unwrap().into()
+        lcm_all(num_steps).into()
     }
 
     fn day() -> u32 {
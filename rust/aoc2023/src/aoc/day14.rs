@@ -1,9 +1,9 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-};
+use std::{cmp::Ordering, collections::BinaryHeap};
 
-use common::problem::day::{AoCProblem, Solution};
+use common::{
+    cycle::nth_state,
+    problem::day::{AoCProblem, Solution},
+};
 
 type Point = (usize, usize);
 
@@ -48,63 +48,20 @@ impl AoCProblem for Day14 {
     }
 
     fn part2(&mut self) -> Solution {
-        // Implementation idea: Keep track of all states (rounded rocks) we've seen so far.
-        // Eventually, we'll reach a state we've seen before.
+        // The rocks eventually settle into a repeating pattern, so we can detect the cycle
+        // and skip ahead instead of spinning a billion times.
         const CYCLES_TO_COMPLETE: usize = 1000000000;
-        let mut rounded_rocks = self.rounded_rocks.clone();
-
-        // Key = state
-        // Value = index that state was seen at
-        let mut seen_states: HashMap<Vec<Point>, usize> = HashMap::new();
-
-        // Figure out where the cycle starts
-        let mut cycles_completed = 0;
-        while cycles_completed < CYCLES_TO_COMPLETE {
-            // north -> west -> south -> east
-            let r1 = tilt_north(&rounded_rocks, &self.cube_rocks);
-            let r2 = tilt_west(&r1, &self.cube_rocks);
-            let r3 = tilt_south(&r2, &self.cube_rocks, self.height);
-            let r4 = tilt_east(&r3, &self.cube_rocks, self.height);
-            rounded_rocks = r4;
-            cycles_completed += 1;
-
-            if seen_states.contains_key(&rounded_rocks) {
-                break;
-            }
-
-            seen_states.insert(rounded_rocks.clone(), cycles_completed);
-        }
-
-        let last_seen_state = seen_states.get(&rounded_rocks).unwrap();
-        let cycle_length = cycles_completed - last_seen_state;
-
-        // Now that we know what the cycle length is, along with the number of cycles completed,
-        // we can "jump" ahead towards the final state
-        //
-        // CYCLES_TO_COMPLETE - cycles_completed
-        // => Gives us the number of cycles left to complete. Note that this does NOT tell us where
-        //    the cycle started, just that the cycle is of that length. So, we'll need to apply an
-        //    offset to get to the correct base state (where the cycle begins).
-        //
-        // ((CYCLES_TO_COMPLETE - cycles_completed) / cycle_length)
-        // => Gives us the number of cycles left to complete, in terms of the cycle length
-        //
-        // ((CYCLES_TO_COMPLETE - cycles_completed) / cycle_length) * cycle_length
-        // => Maps the base index 0 (cycles_completed = 0) to the corresponding index that is before the
-        //    final state.
-        //
-        // ((CYCLES_TO_COMPLETE - cycles_completed) / cycle_length) * cycle_length + last_seen_state
-        // => Correctly maps the previous value so that it maps with the first state in the original cycle.
-        cycles_completed = ((CYCLES_TO_COMPLETE - cycles_completed) / cycle_length) * cycle_length
-            + last_seen_state;
-        while cycles_completed < CYCLES_TO_COMPLETE {
-            let r1 = tilt_north(&rounded_rocks, &self.cube_rocks);
-            let r2 = tilt_west(&r1, &self.cube_rocks);
-            let r3 = tilt_south(&r2, &self.cube_rocks, self.height);
-            let r4 = tilt_east(&r3, &self.cube_rocks, self.height);
-            rounded_rocks = r4;
-            cycles_completed += 1;
-        }
+        let rounded_rocks = nth_state(
+            self.rounded_rocks.clone(),
+            |rocks| {
+                // north -> west -> south -> east
+                let r1 = tilt_north(rocks, &self.cube_rocks);
+                let r2 = tilt_west(&r1, &self.cube_rocks);
+                let r3 = tilt_south(&r2, &self.cube_rocks, self.height);
+                tilt_east(&r3, &self.cube_rocks, self.height)
+            },
+            CYCLES_TO_COMPLETE,
+        );
 
         // Compute the total load
         let mut total_load = 0;
@@ -77,7 +77,8 @@ impl AoCProblem for Day05 {
                 }
             }
 
-            let ordering = topological_sort(&isolated_ordering);
+            let ordering = topological_sort(&isolated_ordering)
+                .expect("ordering rules for an update should not be cyclic");
             let mut new_update = vec![];
             for o in ordering {
                 if self.updates[*bad_idx].contains(o) {
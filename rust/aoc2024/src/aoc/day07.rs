@@ -1,3 +1,4 @@
+use common::numbers::digits::concat;
 use common::problem::day::{AoCProblem, Solution};
 
 pub struct Day07 {
@@ -69,7 +70,7 @@ impl Op {
             Op::Multiply => num1 * num2,
             // This is a fancier & faster  way of doing
             // format!("{num1}{num2}").parse::<usize>().unwrap()
-            Op::Concat => num1 * 10_usize.pow(num2.ilog10() + 1) + num2,
+            Op::Concat => concat(num1, num2, 10),
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use common::numbers::digits::{num_digits, split_at_digit};
 use common::problem::day::{AoCProblem, Solution};
 
 pub struct Day11 {
@@ -65,11 +66,10 @@ fn build_number_graph(num: usize, graph: &mut HashMap<usize, Vec<usize>>) {
         graph.insert(num, vec![1]);
         build_number_graph(1, graph);
     } else {
-        let digits = get_digits(num);
+        let num_digits = num_digits(num, 10);
 
-        if digits.len() % 2 == 0 {
-            let left = combine_digits(&digits[..digits.len() / 2]);
-            let right = combine_digits(&digits[digits.len() / 2..]);
+        if num_digits.is_multiple_of(2) {
+            let (left, right) = split_at_digit(num, num_digits / 2, 10);
             graph.insert(num, vec![left, right]);
             build_number_graph(left, graph);
             build_number_graph(right, graph);
@@ -179,41 +179,3 @@ fn blink(stones: &[usize], num_times: usize) -> usize {
 
     sum
 }
-
-/// Combines a list of digits into a number.
-///
-/// # Parameters
-/// - `nums`: The list of digits.
-///
-/// # Returns
-/// The number with those digits in that order.
-fn combine_digits(digits: &[usize]) -> usize {
-    let mut combined = 0;
-    for num in digits {
-        combined = combined * 10 + *num;
-    }
-
-    combined
-}
-
-/// Gets all digits in a number.
-///
-/// # Parameters
-/// - `num`: The number.
-///
-/// # Returns
-/// The digits in the number.
-fn get_digits(mut num: usize) -> Vec<usize> {
-    let mut stack = vec![];
-    while num > 0 {
-        stack.push(num % 10);
-        num /= 10;
-    }
-
-    let mut digits = vec![];
-    while let Some(s) = stack.pop() {
-        digits.push(s);
-    }
-
-    digits
-}
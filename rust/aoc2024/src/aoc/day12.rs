@@ -1,6 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use common::problem::day::{AoCProblem, Solution};
+use common::{
+    dsu::GridDisjointSet,
+    problem::day::{AoCProblem, Solution},
+};
 
 pub struct Day12 {
     garden_plots: Vec<Vec<char>>,
@@ -14,26 +17,40 @@ impl AoCProblem for Day12 {
     }
 
     fn part1(&mut self) -> Solution {
-        let mut price = 0;
-        let mut explored = HashSet::new();
-        for (row_idx, row) in self.garden_plots.iter().enumerate() {
-            for (col_idx, plant) in row.iter().enumerate() {
-                if explored.contains(&(row_idx as isize, col_idx as isize)) {
-                    continue;
+        // Group the plots into regions, and tally up each plot's contribution to the
+        // perimeter of its region (i.e., the number of sides not shared with the same plant).
+        let (rows, cols) = (self.garden_plots.len(), self.garden_plots[0].len());
+        let mut regions = GridDisjointSet::new(rows, cols);
+        let mut plot_perimeters = vec![];
+        for (i, row) in self.garden_plots.iter().enumerate() {
+            for (j, plant) in row.iter().enumerate() {
+                let mut perimeter = 4;
+                for (di, dj) in DIRECTIONS {
+                    let (ni, nj) = (i as isize + di, j as isize + dj);
+                    if is_out_of_bounds(&self.garden_plots, ni, nj)
+                        || self.garden_plots[ni as usize][nj as usize] != *plant
+                    {
+                        continue;
+                    }
+
+                    regions.union((i, j), (ni as usize, nj as usize));
+                    perimeter -= 1;
                 }
 
-                let (perimeter, seen) = calculate_area_perimeter(
-                    &self.garden_plots,
-                    row_idx as isize,
-                    col_idx as isize,
-                    *plant,
-                );
-                price += perimeter * seen.len();
-                explored.extend(seen);
+                plot_perimeters.push(((i, j), perimeter));
             }
         }
 
-        price.into()
+        let mut region_perimeters: HashMap<(usize, usize), usize> = HashMap::new();
+        for (plot, perimeter) in plot_perimeters {
+            *region_perimeters.entry(regions.find(plot)).or_default() += perimeter;
+        }
+
+        region_perimeters
+            .into_iter()
+            .map(|(root, perimeter)| regions.set_size(root) * perimeter)
+            .sum::<usize>()
+            .into()
     }
 
     fn part2(&mut self) -> Solution {
@@ -1,8 +1,8 @@
-use common::problem::day::{AoCProblem, Solution};
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
+use common::{
+    graphs::dijkstra,
+    problem::day::{AoCProblem, Solution},
 };
+use std::collections::{BinaryHeap, HashSet};
 
 pub struct Day16 {
     maze: Vec<Vec<char>>,
@@ -42,99 +42,48 @@ impl AoCProblem for Day16 {
     }
 
     fn part2(&mut self) -> Solution {
-        #[derive(PartialEq, Eq)]
-        struct PointCostHistory {
-            pt: PointCost,
-            history: HashSet<(usize, usize)>,
-        }
-
-        impl Ord for PointCostHistory {
-            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-                self.pt.cmp(&other.pt)
-            }
-        }
-
-        impl PartialOrd for PointCostHistory {
-            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                Some(self.cmp(other))
-            }
-        }
-
-        let mut all_paths = vec![];
-
-        let mut min_cost = usize::MAX;
-        let mut node_cost_map: HashMap<(usize, usize, (isize, isize)), usize> = HashMap::new();
-        let mut heap = BinaryHeap::new();
-        heap.push(PointCostHistory {
-            pt: PointCost::new(self.maze.len() - 2, 1, Direction::East),
-            history: HashSet::new(),
-        });
-
-        while let Some(mut p) = heap.pop() {
-            if self.maze[p.pt.i][p.pt.j] == '#' {
-                continue;
-            }
-
-            if p.pt.cost > min_cost {
-                continue;
-            }
-
-            p.history.insert((p.pt.i, p.pt.j));
-
-            if self.maze[p.pt.i][p.pt.j] == 'E' {
-                // If we reach the end, check if the cost to reach the end is better than
-                // what we had previously.
-                //
-                // If so, then reset any existing paths and save the new path and cost.
-                // If they're equal, then we can save the existing path.
-                match p.pt.cost.cmp(&min_cost) {
-                    Ordering::Equal => {
-                        all_paths.push(p.history);
-                    }
-                    Ordering::Less => {
-                        min_cost = p.pt.cost;
-                        all_paths = vec![p.history];
-                    }
-                    _ => {}
+        // Rather than carrying the history of every path around, record every equal-cost
+        // predecessor during the search and walk those back from the end tile afterwards.
+        let start = (self.maze.len() - 2, 1, Direction::East);
+        let paths = dijkstra(
+            start,
+            |&(i, j, dir)| {
+                let (di, dj) = dir.directional_vector();
+                let (ni, nj) = ((i as isize + di) as usize, (j as isize + dj) as usize);
+                let mut neighbors = vec![
+                    ((i, j, dir.clockwise()), 1000),
+                    ((i, j, dir.counterclockwise()), 1000),
+                ];
+                if self.maze[ni][nj] != '#' {
+                    neighbors.push(((ni, nj, dir), 1));
                 }
 
-                continue;
-            }
-
-            // Check to see if, at this node, we've already seen a better costing node.
-            // If so, we can skip this iteration. If not, then we can save it and move on.
-            let cost_at_node = node_cost_map
-                .entry((p.pt.i, p.pt.j, p.pt.dir.directional_vector()))
-                .or_insert(usize::MAX);
-            if p.pt.cost > *cost_at_node {
-                continue;
-            }
-
-            *cost_at_node = p.pt.cost;
-
-            // Consider all possible neighbors
-            for neighbor in [
-                p.pt.walk(),
-                p.pt.turn_clockwise().walk(),
-                p.pt.turn_counterclockwise().walk(),
-            ] {
-                if p.history.contains(&(neighbor.i, neighbor.j)) {
-                    continue;
-                }
-
-                heap.push(PointCostHistory {
-                    pt: neighbor,
-                    history: p.history.clone(),
-                });
-            }
-        }
-
-        let mut all_pts = HashSet::new();
-        for path in all_paths {
-            all_pts.extend(path);
-        }
-
-        all_pts.len().into()
+                neighbors
+            },
+            true,
+        );
+
+        let (end_i, end_j) = self
+            .maze
+            .iter()
+            .enumerate()
+            .find_map(|(i, row)| row.iter().position(|&c| c == 'E').map(|j| (i, j)))
+            .expect("maze should have an end tile");
+        let ends = [
+            Direction::West,
+            Direction::South,
+            Direction::North,
+            Direction::East,
+        ]
+        .map(|dir| (end_i, end_j, dir));
+
+        paths
+            .nodes_on_paths_to(&ends)
+            .into_iter()
+            .map(|(i, j, _)| (i, j))
+            .collect::<HashSet<_>>()
+            .len()
+            .into()
     }
 
     fn day() -> u32 {
@@ -0,0 +1,180 @@
+//! Benchmarks for the Intcode computer. Run with `cargo bench`.
+//!
+//! Puzzle inputs can't be shared, so by default this runs an assembled program that works like
+//! the 2019 day 9 BOOST program: it self-tests every opcode and parameter mode (including
+//! large numbers and memory past the end of the program), then spends its time on
+//! relative-mode calls and returns. To also benchmark BOOST itself, set `INTCODE_BOOST` to
+//! the path of a day 9 input.
+//!
+//! Each program is run both with and without instructions decoded ahead of time. Running
+//! without them still benefits from the rest of the engine, so for reference, here's how long
+//! sensor boost mode took per run with the older engine, which decoded every instruction into
+//! a heap-allocated list of digits, compared to this one:
+//!
+//! | Program     | Older engine | This engine, decoded |
+//! |-------------|--------------|----------------------|
+//! | BOOST-like  | 744ms        | 161ms                |
+//! | Real BOOST  | 186ms        | 64ms                 |
+
+use common::intcode::{assemble, parse_intcode, IntCodeComputer};
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Computes the input-th Fibonacci number using a recursive function, where each call frame
+/// holds the return address, the argument and a temporary value.
+const FIBONACCI: &str = "
+    ARB #stack
+    IN -> [rb+1]
+    ADD #done, #0 -> [rb]
+    JT #1, #fib
+done: OUT [rb+1]
+    HALT
+
+fib: LT [rb+1], #2 -> [rb+2]
+    JT [rb+2], #return
+    ADD [rb+1], #-1 -> [rb+4]
+    ADD #first, #0 -> [rb+3]
+    ARB #3
+    JT #1, #fib
+first: ARB #-3
+    ADD [rb+4], #0 -> [rb+2]
+    ADD [rb+1], #-2 -> [rb+4]
+    ADD #second, #0 -> [rb+3]
+    ARB #3
+    JT #1, #fib
+second: ARB #-3
+    ADD [rb+2], [rb+4] -> [rb+1]
+return: JT #1, [rb]
+stack: DATA 0
+";
+
+/// Works like BOOST: with input `1`, it runs a self-test that outputs the opcode of any
+/// instruction that misbehaves, followed by a keycode. With input `2`, it also computes the
+/// 27th Fibonacci number recursively, with its stack past the end of the program.
+const BOOST_LIKE: &str = "
+    ARB #1000
+    IN -> [rb]
+    MUL #34463338, #34463338 -> [rb+1]
+    EQ [rb+1], #1187721666102244 -> [rb+2]
+    JT [rb+2], #relative
+    OUT #2
+relative: ADD #5, #0 -> [rb+3]
+    ARB #3
+    EQ [rb], #5 -> [rb+1]
+    ARB #-3
+    JT [rb+4], #jumps
+    OUT #9
+jumps: JF #0, #less
+    OUT #6
+less: LT #-1, #1 -> [rb+1]
+    JT [rb+1], #tested
+    OUT #7
+tested: EQ [rb], #1 -> [rb+1]
+    JF [rb+1], #boost
+    OUT #1125899906842624
+    HALT
+
+boost: ADD #27, #0 -> [rb+1]
+    ADD #done, #0 -> [rb]
+    JT #1, #fib
+done: OUT [rb+1]
+    HALT
+
+fib: LT [rb+1], #2 -> [rb+2]
+    JT [rb+2], #return
+    ADD [rb+1], #-1 -> [rb+4]
+    ADD #first, #0 -> [rb+3]
+    ARB #3
+    JT #1, #fib
+first: ARB #-3
+    ADD [rb+4], #0 -> [rb+2]
+    ADD [rb+1], #-2 -> [rb+4]
+    ADD #second, #0 -> [rb+3]
+    ARB #3
+    JT #1, #fib
+second: ARB #-3
+    ADD [rb+2], [rb+4] -> [rb+1]
+return: JT #1, [rb]
+";
+
+/// Runs `f` the given number of times (after a warm-up run) and prints the average time.
+///
+/// # Parameters
+/// - `name`: The name of the benchmark.
+/// - `iterations`: The number of times to run `f`.
+/// - `f`: The code to benchmark.
+///
+/// # Returns
+/// The average time per iteration.
+fn bench<F: FnMut()>(name: &str, iterations: u32, mut f: F) -> Duration {
+    f();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+
+    let per_iteration = start.elapsed() / iterations;
+    println!("{name:<40} {per_iteration:>12.2?} per iteration ({iterations} iterations)");
+    per_iteration
+}
+
+/// Benchmarks running a BOOST-like program in sensor boost mode, both with and without
+/// instructions decoded ahead of time, and prints how much faster decoding ahead of time is.
+///
+/// # Parameters
+/// - `name`: The name of the program.
+/// - `program`: The program.
+/// - `iterations`: The number of times to run the program for each benchmark.
+fn bench_boost(name: &str, program: &[isize], iterations: u32) {
+    let mut times = vec![];
+    for predecoding in [false, true] {
+        let label = if predecoding { "decoded" } else { "undecoded" };
+        times.push(bench(&format!("{name}, {label}"), iterations, || {
+            let mut c = IntCodeComputer::new(program, Some(vec![2]));
+            c.set_predecoding(predecoding);
+            c.run_until_completion();
+            assert_eq!(1, black_box(c.view_stdout()).len());
+        }));
+    }
+
+    println!(
+        "{name:<40} {:>12.2}x faster when decoded",
+        times[0].as_secs_f64() / times[1].as_secs_f64()
+    );
+}
+
+fn main() {
+    let boost_like = assemble(BOOST_LIKE).unwrap();
+    let mut c = IntCodeComputer::new(&boost_like, Some(vec![1]));
+    c.run_until_completion();
+    assert_eq!([1125899906842624].as_slice(), c.view_stdout());
+    bench_boost("BOOST-like, sensor boost mode", &boost_like, 10);
+
+    let fibonacci = assemble(FIBONACCI).unwrap();
+    bench("fibonacci(25), new computer", 10, || {
+        let mut c = IntCodeComputer::new(&fibonacci, Some(vec![25]));
+        c.run_until_completion();
+        assert_eq!(Some(&75025), black_box(c.view_stdout().last()));
+    });
+
+    // A large program where each run only touches a little memory, like trying every input of
+    // a brute-force search.
+    let mut large = fibonacci.clone();
+    large.resize(200_000, 0);
+    let mut c = IntCodeComputer::new(&large, None);
+    bench("fibonacci(5) in 200k words, reset", 2_000, || {
+        c.reset();
+        c.input_to_stdin(5);
+        c.run_until_completion();
+        assert_eq!(Some(&5), black_box(c.view_stdout().last()));
+    });
+
+    if let Ok(path) = env::var("INTCODE_BOOST") {
+        let boost = parse_intcode(fs::read_to_string(path).unwrap().trim());
+        bench_boost("day 9 BOOST, sensor boost mode", &boost, 10);
+    } else {
+        println!("(set INTCODE_BOOST to the path of a 2019 day 9 input to benchmark BOOST)");
+    }
+}
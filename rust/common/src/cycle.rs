@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in the sequence of states `x0, x1 = f(x0), x2 = f(x1), ...` produced by repeatedly
+/// applying a step function to some initial state.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    start: usize,
+    /// The number of states in the cycle.
+    length: usize,
+}
+
+impl Cycle {
+    /// Gets the index of the first state that is part of the cycle (often called `μ`). Every
+    /// state before this index is only seen once.
+    ///
+    /// # Returns
+    /// The index of the first state in the cycle.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Gets the length of the cycle (often called `λ`). For every `i >= start`, the state at
+    /// index `i + length` is the same as the state at index `i`.
+    ///
+    /// # Returns
+    /// The length of the cycle.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Maps the given index to the earliest index with the same state.
+    ///
+    /// # Parameters
+    /// - `n`: The index of a state in the sequence.
+    ///
+    /// # Returns
+    /// An index that is at most `start + length - 1`, whose state is the same as the state at
+    /// index `n`.
+    pub fn equivalent_index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle in a sequence of states using Floyd's "tortoise and hare" algorithm. This
+/// only needs to keep a couple of states around at a time, but calls `step` roughly three
+/// times as often as [`detect_cycle`]. The sequence must eventually repeat, or else this never
+/// returns.
+///
+/// # Parameters
+/// - `initial`: The initial state.
+/// - `step`: The function that computes the next state from the current one.
+///
+/// # Returns
+/// The cycle.
+pub fn floyd<T, F>(initial: T, mut step: F) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    // Find some index `i` where x_i = x_2i; this must be a multiple of the cycle length.
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // Moving one pointer back to the start and stepping both at the same speed makes them meet
+    // at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle in a sequence of states using Brent's algorithm. Like [`floyd`], this only
+/// keeps a couple of states around at a time, but it usually calls `step` fewer times. The
+/// sequence must eventually repeat, or else this never returns.
+///
+/// # Parameters
+/// - `initial`: The initial state.
+/// - `step`: The function that computes the next state from the current one.
+///
+/// # Returns
+/// The cycle.
+pub fn brent<T, F>(initial: T, mut step: F) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    // Search successive powers of two for the cycle length.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Start the hare `length` steps ahead of the tortoise; they then meet at the start of the
+    // cycle.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle in a sequence of states by remembering the index of every state seen so far.
+/// This calls `step` as few times as possible, at the cost of storing every state. The sequence
+/// must eventually repeat, or else this never returns.
+///
+/// # Parameters
+/// - `initial`: The initial state.
+/// - `step`: The function that computes the next state from the current one.
+///
+/// # Returns
+/// The cycle.
+pub fn detect_cycle<T, F>(initial: T, step: F) -> Cycle
+where
+    T: Clone + Hash + Eq,
+    F: FnMut(&T) -> T,
+{
+    detect_cycle_by_key(initial, step, |s| s.clone())
+}
+
+/// Finds the cycle in a sequence of states by remembering a key for every state seen so far.
+/// This is useful when states are large but can be identified by something smaller (e.g., a
+/// hash or a handful of fields); two states with the same key are assumed to be the same.
+///
+/// # Parameters
+/// - `initial`: The initial state.
+/// - `step`: The function that computes the next state from the current one.
+/// - `key`: The function that identifies a state.
+///
+/// # Returns
+/// The cycle.
+pub fn detect_cycle_by_key<T, K, F, P>(initial: T, mut step: F, mut key: P) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(&T) -> T,
+    P: FnMut(&T) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut state = initial;
+    let mut idx = 0;
+    loop {
+        let k = key(&state);
+        if let Some(&start) = seen.get(&k) {
+            return Cycle {
+                start,
+                length: idx - start,
+            };
+        }
+
+        seen.insert(k, idx);
+        state = step(&state);
+        idx += 1;
+    }
+}
+
+/// Finds the shortest cycle in some already-sampled states, e.g., the output of a simulation
+/// that was run for a while. Unlike the other detectors, this doesn't need a step function,
+/// but the states after the cycle starts must repeat at least twice in the samples.
+///
+/// # Parameters
+/// - `states`: The states, where `states[i]` is the state at index `i`.
+///
+/// # Returns
+/// The cycle with the shortest length (and, for that length, the earliest start), or `None`
+/// if the samples don't repeat.
+pub fn find_cycle<T: PartialEq>(states: &[T]) -> Option<Cycle> {
+    (1..=states.len() / 2).find_map(|length| {
+        // Walk backwards from the end for as long as the states keep repeating.
+        let mut start = states.len() - length;
+        while start > 0 && states[start - 1] == states[start - 1 + length] {
+            start -= 1;
+        }
+
+        (states.len() - start >= 2 * length).then_some(Cycle { start, length })
+    })
+}
+
+/// Computes the state after applying `step` to the initial state `n` times. If the sequence of
+/// states repeats before then, the cycle is detected and skipped over, so `n` can be huge
+/// (e.g., `1_000_000_000`) as long as the cycle is found reasonably quickly.
+///
+/// # Parameters
+/// - `initial`: The initial state.
+/// - `step`: The function that computes the next state from the current one.
+/// - `n`: The number of steps to take.
+///
+/// # Returns
+/// The state after `n` steps.
+pub fn nth_state<T, F>(initial: T, step: F, n: usize) -> T
+where
+    T: Clone + Hash + Eq,
+    F: FnMut(&T) -> T,
+{
+    nth_state_by_key(initial, step, |s| s.clone(), n)
+}
+
+/// Computes the state after applying `step` to the initial state `n` times, using `key` to
+/// detect when the sequence of states repeats. Only the keys are stored, so this is suitable
+/// for large states; two states with the same key are assumed to be the same.
+///
+/// # Parameters
+/// - `initial`: The initial state.
+/// - `step`: The function that computes the next state from the current one.
+/// - `key`: The function that identifies a state.
+/// - `n`: The number of steps to take.
+///
+/// # Returns
+/// The state after `n` steps.
+pub fn nth_state_by_key<T, K, F, P>(initial: T, mut step: F, mut key: P, n: usize) -> T
+where
+    K: Hash + Eq,
+    F: FnMut(&T) -> T,
+    P: FnMut(&T) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut state = initial;
+    let mut idx = 0;
+    while idx < n {
+        let k = key(&state);
+        if let Some(&start) = seen.get(&k) {
+            // The state at `idx` is the same as the state at `start`, so every `idx - start`
+            // steps from here on lead back to the current state. Only the leftover steps need
+            // to be simulated.
+            let remaining = (n - idx) % (idx - start);
+            for _ in 0..remaining {
+                state = step(&state);
+            }
+
+            return state;
+        }
+
+        seen.insert(k, idx);
+        state = step(&state);
+        idx += 1;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        brent, detect_cycle, detect_cycle_by_key, find_cycle, floyd, nth_state, nth_state_by_key,
+        Cycle,
+    };
+
+    /// Starting from 0, this gives the sequence 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 3, 4, ..., which
+    /// enters a cycle of length 7 at index 3.
+    fn step(x: &u64) -> u64 {
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 3][*x as usize]
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for detector in [floyd, brent, detect_cycle] {
+            let cycle = detector(0, step);
+            assert_eq!(3, cycle.start());
+            assert_eq!(7, cycle.length());
+        }
+    }
+
+    #[test]
+    fn test_pure_cycle() {
+        let step = |x: &u32| (x + 1) % 5;
+        for detector in [floyd, brent, detect_cycle] {
+            let cycle = detector(0, step);
+            assert_eq!(0, cycle.start());
+            assert_eq!(5, cycle.length());
+        }
+
+        let fixed = brent(7, |x| *x);
+        assert_eq!(0, fixed.start());
+        assert_eq!(1, fixed.length());
+    }
+
+    #[test]
+    fn test_equivalent_index() {
+        let cycle = detect_cycle(0, step);
+        assert_eq!(2, cycle.equivalent_index(2));
+        assert_eq!(3, cycle.equivalent_index(10));
+        assert_eq!(6, cycle.equivalent_index(1_000_000_000));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut states = vec![0];
+        for i in 0..20 {
+            states.push(step(&states[i]));
+        }
+
+        assert_eq!(Some(detect_cycle(0, step)), find_cycle(&states));
+        assert_eq!(
+            Some(Cycle {
+                start: 0,
+                length: 1
+            }),
+            find_cycle(&[4, 4])
+        );
+
+        // The cycle has to show up twice.
+        assert_eq!(None, find_cycle(&states[..16]));
+        assert_eq!(None, find_cycle(&[1, 2, 3, 1, 2]));
+        assert_eq!(None, find_cycle::<u8>(&[]));
+    }
+
+    #[test]
+    fn test_nth_state() {
+        let mut states = vec![0];
+        for i in 0..30 {
+            states.push(step(&states[i]));
+        }
+
+        for (n, expected) in states.iter().enumerate() {
+            assert_eq!(*expected, nth_state(0, step, n));
+        }
+
+        assert_eq!(states[6], nth_state(0, step, 1_000_000_000));
+    }
+
+    #[test]
+    fn test_nth_state_by_key() {
+        // The counter makes every state unique, but the key only looks at the value.
+        let step = |&(x, steps): &(u64, u64)| (step(&x), steps + 1);
+        let cycle = detect_cycle_by_key((0, 0), step, |s| s.0);
+        assert_eq!(3, cycle.start());
+        assert_eq!(7, cycle.length());
+
+        let (x, steps) = nth_state_by_key((0, 0), step, |s| s.0, 1_000_000_000);
+        assert_eq!(nth_state(0, super::tests::step, 1_000_000_000), x);
+        assert_eq!(10 + (1_000_000_000 - 10) % 7, steps);
+    }
+}
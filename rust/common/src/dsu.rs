@@ -0,0 +1,544 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A disjoint set (union-find) structure over the elements `0, 1, ..., n - 1`. This uses path
+/// compression and union by rank, so every operation runs in nearly constant amortized time.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    /// The parent of each element. An element is the representative of its set if it is its
+    /// own parent.
+    parent: Vec<usize>,
+    /// An upper bound on the height of each representative's tree.
+    rank: Vec<u8>,
+    /// The size of each set, indexed by the set's representative.
+    size: Vec<usize>,
+    /// The number of disjoint sets.
+    num_sets: usize,
+}
+
+impl DisjointSet {
+    /// Creates a new disjoint set structure, where each of the `n` elements starts in its own
+    /// set.
+    ///
+    /// # Parameters
+    /// - `n`: The number of elements.
+    ///
+    /// # Returns
+    /// The disjoint set structure.
+    pub fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            num_sets: n,
+        }
+    }
+
+    /// Adds a new element, in its own set.
+    ///
+    /// # Returns
+    /// The new element.
+    pub fn add(&mut self) -> usize {
+        let element = self.parent.len();
+        self.parent.push(element);
+        self.rank.push(0);
+        self.size.push(1);
+        self.num_sets += 1;
+        element
+    }
+
+    /// Gets the number of elements.
+    ///
+    /// # Returns
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Checks if there are no elements.
+    ///
+    /// # Returns
+    /// `true` if there are no elements, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Gets the number of disjoint sets.
+    ///
+    /// # Returns
+    /// The number of disjoint sets.
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    /// Finds the representative of the set containing the given element.
+    ///
+    /// # Parameters
+    /// - `x`: The element.
+    ///
+    /// # Returns
+    /// The representative. Two elements are in the same set if and only if they have the same
+    /// representative.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the path directly at the root, so later lookups are faster.
+        let mut curr = x;
+        while self.parent[curr] != root {
+            let next = self.parent[curr];
+            self.parent[curr] = root;
+            curr = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing the two given elements.
+    ///
+    /// # Parameters
+    /// - `a`: The first element.
+    /// - `b`: The second element.
+    ///
+    /// # Returns
+    /// `true` if the sets were merged, and `false` if the elements were already in the same
+    /// set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.rank[a] < self.rank[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+
+        self.num_sets -= 1;
+        true
+    }
+
+    /// Checks if the two given elements are in the same set.
+    ///
+    /// # Parameters
+    /// - `a`: The first element.
+    /// - `b`: The second element.
+    ///
+    /// # Returns
+    /// `true` if the elements are in the same set, and `false` otherwise.
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Gets the size of the set containing the given element.
+    ///
+    /// # Parameters
+    /// - `x`: The element.
+    ///
+    /// # Returns
+    /// The number of elements in the set.
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Groups every element by the set it belongs to.
+    ///
+    /// # Returns
+    /// The sets. Each set is sorted, and the sets are ordered by their smallest element.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut set_of_root = HashMap::new();
+        let mut sets: Vec<Vec<usize>> = vec![];
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let idx = *set_of_root.entry(root).or_insert_with(|| {
+                sets.push(vec![]);
+                sets.len() - 1
+            });
+            sets[idx].push(x);
+        }
+
+        sets
+    }
+}
+
+/// A disjoint set structure over arbitrary hashable values. Values are added the first time
+/// they are used.
+#[derive(Clone, Debug)]
+pub struct KeyedDisjointSet<K> {
+    /// The element assigned to each value.
+    ids: HashMap<K, usize>,
+    /// The value of each element.
+    keys: Vec<K>,
+    /// The underlying structure.
+    inner: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        KeyedDisjointSet {
+            ids: HashMap::new(),
+            keys: vec![],
+            inner: DisjointSet::new(0),
+        }
+    }
+}
+
+impl<K> KeyedDisjointSet<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Creates a new, empty, disjoint set structure.
+    ///
+    /// # Returns
+    /// The disjoint set structure.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given value in its own set, if it hasn't been added yet.
+    ///
+    /// # Parameters
+    /// - `key`: The value.
+    ///
+    /// # Returns
+    /// The element of the underlying [`DisjointSet`] that represents this value.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+
+        let id = self.inner.add();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    /// Checks if the given value has been added.
+    ///
+    /// # Parameters
+    /// - `key`: The value.
+    ///
+    /// # Returns
+    /// `true` if the value has been added, and `false` otherwise.
+    pub fn contains(&self, key: &K) -> bool {
+        self.ids.contains_key(key)
+    }
+
+    /// Gets the number of values that have been added.
+    ///
+    /// # Returns
+    /// The number of values.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Checks if no values have been added.
+    ///
+    /// # Returns
+    /// `true` if no values have been added, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Gets the number of disjoint sets.
+    ///
+    /// # Returns
+    /// The number of disjoint sets.
+    pub fn num_sets(&self) -> usize {
+        self.inner.num_sets()
+    }
+
+    /// Finds the representative of the set containing the given value.
+    ///
+    /// # Parameters
+    /// - `key`: The value.
+    ///
+    /// # Returns
+    /// The representative, or `None` if the value hasn't been added.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let id = *self.ids.get(key)?;
+        let root = self.inner.find(id);
+        Some(&self.keys[root])
+    }
+
+    /// Merges the sets containing the two given values, adding the values if needed.
+    ///
+    /// # Parameters
+    /// - `a`: The first value.
+    /// - `b`: The second value.
+    ///
+    /// # Returns
+    /// `true` if the sets were merged, and `false` if the values were already in the same set.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        self.inner.union(a, b)
+    }
+
+    /// Checks if the two given values are in the same set.
+    ///
+    /// # Parameters
+    /// - `a`: The first value.
+    /// - `b`: The second value.
+    ///
+    /// # Returns
+    /// `true` if both values have been added and are in the same set, and `false` otherwise.
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.ids.get(a), self.ids.get(b)) {
+            (Some(&a), Some(&b)) => self.inner.connected(a, b),
+            _ => false,
+        }
+    }
+
+    /// Gets the size of the set containing the given value.
+    ///
+    /// # Parameters
+    /// - `key`: The value.
+    ///
+    /// # Returns
+    /// The number of values in the set, or `0` if the value hasn't been added.
+    pub fn set_size(&mut self, key: &K) -> usize {
+        match self.ids.get(key) {
+            Some(&id) => self.inner.set_size(id),
+            None => 0,
+        }
+    }
+
+    /// Groups every value by the set it belongs to.
+    ///
+    /// # Returns
+    /// The sets. Values are ordered by when they were added.
+    pub fn sets(&mut self) -> Vec<Vec<&K>> {
+        self.inner
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|id| &self.keys[id]).collect())
+            .collect()
+    }
+}
+
+/// A disjoint set structure over the cells of a grid with a fixed number of rows and columns.
+/// Each `(row, col)` coordinate is mapped to the flat index `row * cols + col`.
+#[derive(Clone, Debug)]
+pub struct GridDisjointSet {
+    /// The number of rows in the grid.
+    rows: usize,
+    /// The number of columns in the grid.
+    cols: usize,
+    /// The underlying structure.
+    inner: DisjointSet,
+}
+
+impl GridDisjointSet {
+    /// Creates a new disjoint set structure, where each cell of the grid starts in its own set.
+    ///
+    /// # Parameters
+    /// - `rows`: The number of rows.
+    /// - `cols`: The number of columns.
+    ///
+    /// # Returns
+    /// The disjoint set structure.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        GridDisjointSet {
+            rows,
+            cols,
+            inner: DisjointSet::new(rows * cols),
+        }
+    }
+
+    /// Gets the dimensions of the grid.
+    ///
+    /// # Returns
+    /// A tuple of the number of rows and the number of columns.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Gets the flat index of the given cell.
+    ///
+    /// # Parameters
+    /// - `cell`: The `(row, col)` coordinate of the cell.
+    ///
+    /// # Returns
+    /// The flat index.
+    pub fn index(&self, (row, col): (usize, usize)) -> usize {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is out of bounds"
+        );
+        row * self.cols + col
+    }
+
+    /// Gets the cell at the given flat index.
+    ///
+    /// # Parameters
+    /// - `idx`: The flat index.
+    ///
+    /// # Returns
+    /// The `(row, col)` coordinate of the cell.
+    pub fn cell(&self, idx: usize) -> (usize, usize) {
+        (idx / self.cols, idx % self.cols)
+    }
+
+    /// Gets the number of disjoint sets.
+    ///
+    /// # Returns
+    /// The number of disjoint sets.
+    pub fn num_sets(&self) -> usize {
+        self.inner.num_sets()
+    }
+
+    /// Finds the representative cell of the set containing the given cell.
+    ///
+    /// # Parameters
+    /// - `cell`: The `(row, col)` coordinate of the cell.
+    ///
+    /// # Returns
+    /// The representative cell.
+    pub fn find(&mut self, cell: (usize, usize)) -> (usize, usize) {
+        let idx = self.index(cell);
+        let root = self.inner.find(idx);
+        self.cell(root)
+    }
+
+    /// Merges the sets containing the two given cells.
+    ///
+    /// # Parameters
+    /// - `a`: The first cell.
+    /// - `b`: The second cell.
+    ///
+    /// # Returns
+    /// `true` if the sets were merged, and `false` if the cells were already in the same set.
+    pub fn union(&mut self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let (a, b) = (self.index(a), self.index(b));
+        self.inner.union(a, b)
+    }
+
+    /// Checks if the two given cells are in the same set.
+    ///
+    /// # Parameters
+    /// - `a`: The first cell.
+    /// - `b`: The second cell.
+    ///
+    /// # Returns
+    /// `true` if the cells are in the same set, and `false` otherwise.
+    pub fn connected(&mut self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let (a, b) = (self.index(a), self.index(b));
+        self.inner.connected(a, b)
+    }
+
+    /// Gets the size of the set containing the given cell.
+    ///
+    /// # Parameters
+    /// - `cell`: The `(row, col)` coordinate of the cell.
+    ///
+    /// # Returns
+    /// The number of cells in the set.
+    pub fn set_size(&mut self, cell: (usize, usize)) -> usize {
+        let idx = self.index(cell);
+        self.inner.set_size(idx)
+    }
+
+    /// Groups every cell by the set it belongs to.
+    ///
+    /// # Returns
+    /// The sets. Each set is in row-major order, and the sets are ordered by their first cell.
+    pub fn sets(&mut self) -> Vec<Vec<(usize, usize)>> {
+        let cols = self.cols;
+        self.inner
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|i| (i / cols, i % cols)).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisjointSet, GridDisjointSet, KeyedDisjointSet};
+
+    #[test]
+    fn test_union_find() {
+        let mut dsu = DisjointSet::new(6);
+        assert_eq!(6, dsu.num_sets());
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected(0, 4));
+        assert_eq!(4, dsu.set_size(2));
+        assert_eq!(1, dsu.set_size(5));
+        assert_eq!(3, dsu.num_sets());
+        assert_eq!(vec![vec![0, 1, 2, 3], vec![4], vec![5]], dsu.sets());
+
+        let new = dsu.add();
+        assert_eq!(6, new);
+        assert!(dsu.union(new, 4));
+        assert_eq!(3, dsu.num_sets());
+        assert_eq!(2, dsu.set_size(4));
+    }
+
+    #[test]
+    fn test_keyed() {
+        let mut dsu = KeyedDisjointSet::new();
+        dsu.union("a", "b");
+        dsu.union("c", "d");
+        dsu.insert("e");
+        assert_eq!(5, dsu.len());
+        assert_eq!(3, dsu.num_sets());
+        assert!(dsu.connected(&"a", &"b"));
+        assert!(!dsu.connected(&"a", &"c"));
+        assert!(!dsu.connected(&"a", &"z"));
+        assert_eq!(dsu.find(&"c").copied(), dsu.find(&"d").copied());
+        assert_eq!(None, dsu.find(&"z"));
+        assert_eq!(0, dsu.set_size(&"z"));
+
+        dsu.union("b", "d");
+        assert_eq!(4, dsu.set_size(&"c"));
+        assert_eq!(vec![vec![&"a", &"b", &"c", &"d"], vec![&"e"]], dsu.sets());
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = ["AAB", "ABB", "CCB"]
+            .map(|r| r.chars().collect::<Vec<_>>())
+            .to_vec();
+        let mut dsu = GridDisjointSet::new(3, 3);
+        for i in 0..3 {
+            for j in 0..3 {
+                if i + 1 < 3 && grid[i][j] == grid[i + 1][j] {
+                    dsu.union((i, j), (i + 1, j));
+                }
+
+                if j + 1 < 3 && grid[i][j] == grid[i][j + 1] {
+                    dsu.union((i, j), (i, j + 1));
+                }
+            }
+        }
+
+        assert_eq!(3, dsu.num_sets());
+        assert_eq!(4, dsu.set_size((2, 2)));
+        assert!(dsu.connected((0, 2), (1, 1)));
+        assert!(!dsu.connected((0, 0), (1, 1)));
+        assert_eq!(dsu.find((2, 0)), dsu.find((2, 1)));
+        assert_eq!(5, dsu.index((1, 2)));
+        assert_eq!((1, 2), dsu.cell(5));
+        assert_eq!(
+            vec![
+                vec![(0, 0), (0, 1), (1, 0)],
+                vec![(0, 2), (1, 1), (1, 2), (2, 2)],
+                vec![(2, 0), (2, 1)]
+            ],
+            dsu.sets()
+        );
+    }
+}
@@ -0,0 +1,924 @@
+use crate::dsu::DisjointSet;
+use std::cmp::min;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ops::Add;
+
+/// The identifier of a node in a [`Graph`]. IDs are handed out in the order that nodes are
+/// added, starting from `0`, so they can also be used to index into per-node `Vec`s.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Gets the index of this node.
+    ///
+    /// # Returns
+    /// The index of this node, which is in the range `[0, node_count)`.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// A graph whose nodes are identified by name. Each name is interned into a [`NodeId`] when it
+/// is first added, so the algorithms on this graph never have to hash or compare strings.
+///
+/// Each node holds some data of type `N`, and each edge holds some data (usually a weight) of
+/// type `E`.
+#[derive(Clone, Debug)]
+pub struct Graph<N, E> {
+    /// Whether edges only go one way.
+    directed: bool,
+    /// The name of each node, indexed by ID.
+    names: Vec<String>,
+    /// The ID of each node, keyed by name.
+    ids: HashMap<String, NodeId>,
+    /// The data of each node, indexed by ID.
+    data: Vec<N>,
+    /// The outgoing edges of each node, indexed by ID.
+    edges: Vec<Vec<(NodeId, E)>>,
+    /// The number of edges that have been added.
+    edge_count: usize,
+}
+
+impl<N, E> Graph<N, E> {
+    /// Creates a new, empty, directed graph.
+    ///
+    /// # Returns
+    /// The graph.
+    pub fn new_directed() -> Self {
+        Self::new(true)
+    }
+
+    /// Creates a new, empty, undirected graph.
+    ///
+    /// # Returns
+    /// The graph.
+    pub fn new_undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            names: vec![],
+            ids: HashMap::new(),
+            data: vec![],
+            edges: vec![],
+            edge_count: 0,
+        }
+    }
+
+    /// Whether this graph is directed.
+    ///
+    /// # Returns
+    /// `true` if edges only go one way, and `false` otherwise.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Adds a node with the given name to the graph. If a node with this name already exists,
+    /// its data is replaced instead.
+    ///
+    /// # Parameters
+    /// - `name`: The name of the node.
+    /// - `data`: The data to store with the node.
+    ///
+    /// # Returns
+    /// The ID of the node.
+    pub fn add_node(&mut self, name: &str, data: N) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            self.data[id.0] = data;
+            return id;
+        }
+
+        let id = NodeId(self.names.len());
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.data.push(data);
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// Gets the ID of the node with the given name.
+    ///
+    /// # Parameters
+    /// - `name`: The name of the node.
+    ///
+    /// # Returns
+    /// The ID of the node, or `None` if no such node exists.
+    pub fn node_id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Gets the name of the given node.
+    ///
+    /// # Parameters
+    /// - `id`: The ID of the node.
+    ///
+    /// # Returns
+    /// The name of the node.
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id.0]
+    }
+
+    /// Gets the data stored with the given node.
+    ///
+    /// # Parameters
+    /// - `id`: The ID of the node.
+    ///
+    /// # Returns
+    /// The data.
+    pub fn data(&self, id: NodeId) -> &N {
+        &self.data[id.0]
+    }
+
+    /// Gets a mutable reference to the data stored with the given node.
+    ///
+    /// # Parameters
+    /// - `id`: The ID of the node.
+    ///
+    /// # Returns
+    /// The data.
+    pub fn data_mut(&mut self, id: NodeId) -> &mut N {
+        &mut self.data[id.0]
+    }
+
+    /// Gets the number of nodes in this graph.
+    ///
+    /// # Returns
+    /// The number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Gets the number of edges in this graph. For undirected graphs, an edge between two nodes
+    /// is only counted once.
+    ///
+    /// # Returns
+    /// The number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Gets the IDs of every node in this graph.
+    ///
+    /// # Returns
+    /// An iterator over the IDs, in the order that the nodes were added.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.names.len()).map(NodeId)
+    }
+
+    /// Gets the neighbors of the given node, i.e., the nodes that it has an edge to.
+    ///
+    /// # Parameters
+    /// - `id`: The ID of the node.
+    ///
+    /// # Returns
+    /// An iterator over each neighbor and the data of the edge to that neighbor.
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> {
+        self.edges[id.0].iter().map(|(n, e)| (*n, e))
+    }
+
+    /// Gets the number of edges leaving the given node.
+    ///
+    /// # Parameters
+    /// - `id`: The ID of the node.
+    ///
+    /// # Returns
+    /// The number of outgoing edges.
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.edges[id.0].len()
+    }
+
+    /// Gets the neighbors of every node, ignoring the direction of edges and any self-loops.
+    ///
+    /// # Returns
+    /// The neighbors of each node, indexed by ID.
+    fn undirected_neighbors(&self) -> Vec<HashSet<usize>> {
+        let mut neighbors = vec![HashSet::new(); self.node_count()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(NodeId(to), _) in edges {
+                if from != to {
+                    neighbors[from].insert(to);
+                    neighbors[to].insert(from);
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Finds the connected components of this graph using a union-find structure. For directed
+    /// graphs, the direction of edges is ignored (i.e., this finds the weakly connected
+    /// components).
+    ///
+    /// # Returns
+    /// The components. Each component is sorted by ID, and the components are ordered by their
+    /// smallest ID.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut dsu = DisjointSet::new(self.node_count());
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(NodeId(to), _) in edges {
+                dsu.union(from, to);
+            }
+        }
+
+        dsu.sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(NodeId).collect())
+            .collect()
+    }
+
+    /// Finds the strongly connected components of this graph using Tarjan's algorithm.
+    ///
+    /// # Returns
+    /// The components, in reverse topological order; that is, no component has an edge to a
+    /// component that comes after it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        const UNVISITED: usize = usize::MAX;
+        let n = self.node_count();
+        let mut index = vec![UNVISITED; n];
+        let mut low_link = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = vec![];
+        let mut next_index = 0;
+        let mut components = vec![];
+
+        for root in 0..n {
+            if index[root] != UNVISITED {
+                continue;
+            }
+
+            // An explicit stack of (node, index of the next edge to explore) is used instead of
+            // recursion so that long chains can't overflow the call stack.
+            let mut call_stack = vec![(root, 0)];
+            index[root] = next_index;
+            low_link[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(v, i)) = call_stack.last() {
+                if let Some(&(NodeId(w), _)) = self.edges[v].get(i) {
+                    call_stack.last_mut().unwrap().1 += 1;
+                    if index[w] == UNVISITED {
+                        index[w] = next_index;
+                        low_link[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call_stack.push((w, 0));
+                    } else if on_stack[w] {
+                        low_link[v] = min(low_link[v], index[w]);
+                    }
+
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(u, _)) = call_stack.last() {
+                    low_link[u] = min(low_link[u], low_link[v]);
+                }
+
+                if low_link[v] == index[v] {
+                    let mut component = vec![];
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack[w] = false;
+                        component.push(NodeId(w));
+                        if w == v {
+                            break;
+                        }
+                    }
+
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Finds every path from `from` to `to`, where each step onto a node must be allowed by
+    /// `can_visit`. Paths end as soon as they reach `to`.
+    ///
+    /// The predicate is given the node to step onto and the number of times each node (indexed
+    /// by [`NodeId::index`]) has been visited so far on the current path. For example, to only
+    /// allow simple paths, use `|id, visits| visits[id.index()] == 0`.
+    ///
+    /// # Parameters
+    /// - `from`: The node to start from. This counts as visited once.
+    /// - `to`: The node to end at.
+    /// - `can_visit`: Whether a node can be stepped onto, given the current visit counts.
+    ///
+    /// # Returns
+    /// All paths, each starting with `from` and ending with `to`.
+    pub fn simple_paths<F>(&self, from: NodeId, to: NodeId, can_visit: F) -> Vec<Vec<NodeId>>
+    where
+        F: FnMut(NodeId, &[usize]) -> bool,
+    {
+        let mut paths = vec![];
+        self.walk_paths(from, to, can_visit, |path| paths.push(path.to_vec()));
+        paths
+    }
+
+    /// Counts the paths from `from` to `to`, where each step onto a node must be allowed by
+    /// `can_visit`. This is like [`Graph::simple_paths`], but without storing each path.
+    ///
+    /// # Parameters
+    /// - `from`: The node to start from. This counts as visited once.
+    /// - `to`: The node to end at.
+    /// - `can_visit`: Whether a node can be stepped onto, given the current visit counts.
+    ///
+    /// # Returns
+    /// The number of paths.
+    pub fn count_simple_paths<F>(&self, from: NodeId, to: NodeId, can_visit: F) -> usize
+    where
+        F: FnMut(NodeId, &[usize]) -> bool,
+    {
+        let mut count = 0;
+        self.walk_paths(from, to, can_visit, |_| count += 1);
+        count
+    }
+
+    fn walk_paths<F, P>(&self, from: NodeId, to: NodeId, mut can_visit: F, mut on_path: P)
+    where
+        F: FnMut(NodeId, &[usize]) -> bool,
+        P: FnMut(&[NodeId]),
+    {
+        let mut visits = vec![0; self.node_count()];
+        visits[from.0] = 1;
+        let mut path = vec![from];
+        if from == to {
+            on_path(&path);
+            return;
+        }
+
+        // Each stack entry is the index of the next edge to try from the node at the same
+        // position in `path`.
+        let mut next_edge = vec![0];
+        while let Some(&NodeId(curr)) = path.last() {
+            let idx = next_edge.last_mut().unwrap();
+            let Some(&(next, _)) = self.edges[curr].get(*idx) else {
+                visits[curr] -= 1;
+                path.pop();
+                next_edge.pop();
+                continue;
+            };
+
+            *idx += 1;
+            if !can_visit(next, &visits) {
+                continue;
+            }
+
+            path.push(next);
+            if next == to {
+                on_path(&path);
+                path.pop();
+                continue;
+            }
+
+            visits[next.0] += 1;
+            next_edge.push(0);
+        }
+    }
+
+    /// Finds every maximal clique in this graph using the Bron–Kerbosch algorithm with
+    /// pivoting. The direction of edges is ignored.
+    ///
+    /// # Returns
+    /// The maximal cliques, each sorted by ID.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let neighbors = self.undirected_neighbors();
+        let mut cliques = vec![];
+        bron_kerbosch(
+            &neighbors,
+            &mut vec![],
+            (0..self.node_count()).collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+        cliques
+    }
+
+    /// Finds a largest clique in this graph. The direction of edges is ignored.
+    ///
+    /// # Returns
+    /// The clique, sorted by ID. This is empty if the graph has no nodes.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(|c| c.len())
+            .unwrap_or_default()
+    }
+}
+
+impl<N: Default, E> Graph<N, E> {
+    /// Gets the ID of the node with the given name, adding the node (with default data) if it
+    /// doesn't exist yet.
+    ///
+    /// # Parameters
+    /// - `name`: The name of the node.
+    ///
+    /// # Returns
+    /// The ID of the node.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        match self.node_id(name) {
+            Some(id) => id,
+            None => self.add_node(name, N::default()),
+        }
+    }
+}
+
+impl<N, E: Clone> Graph<N, E> {
+    /// Adds an edge between two nodes. For undirected graphs, the edge can be traversed in
+    /// both directions.
+    ///
+    /// # Parameters
+    /// - `from`: The node that the edge starts at.
+    /// - `to`: The node that the edge ends at.
+    /// - `data`: The data (e.g., the weight) of the edge.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, data: E) {
+        if !self.directed && from != to {
+            self.edges[to.0].push((from, data.clone()));
+        }
+
+        self.edges[from.0].push((to, data));
+        self.edge_count += 1;
+    }
+}
+
+impl<N: Default, E: Clone> Graph<N, E> {
+    /// Adds an edge between two nodes, identified by name. Nodes that don't exist yet are added
+    /// with default data.
+    ///
+    /// # Parameters
+    /// - `from`: The name of the node that the edge starts at.
+    /// - `to`: The name of the node that the edge ends at.
+    /// - `data`: The data (e.g., the weight) of the edge.
+    ///
+    /// # Returns
+    /// The IDs of the two nodes.
+    pub fn add_edge_by_name(&mut self, from: &str, to: &str, data: E) -> (NodeId, NodeId) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        self.add_edge(from, to, data);
+        (from, to)
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    E: Copy + Ord + Add<Output = E> + Default,
+{
+    /// Finds a minimum cut of this graph using the Stoer–Wagner algorithm; that is, a way to
+    /// split the nodes into two non-empty groups such that the total weight of the edges
+    /// between the groups is as small as possible. The direction of edges is ignored, and
+    /// parallel edges have their weights added together.
+    ///
+    /// # Returns
+    /// The weight of the cut and the nodes on one side of it (sorted by ID), or `None` if the
+    /// graph has fewer than two nodes.
+    pub fn min_cut(&self) -> Option<(E, Vec<NodeId>)> {
+        let n = self.node_count();
+        if n < 2 {
+            return None;
+        }
+
+        let mut weights: Vec<HashMap<usize, E>> = vec![HashMap::new(); n];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(NodeId(to), weight) in edges {
+                if from == to {
+                    continue;
+                }
+
+                let w = weights[from].entry(to).or_default();
+                *w = *w + weight;
+                // Undirected edges are stored in both directions, so they only need to be
+                // mirrored here for directed graphs.
+                if self.directed {
+                    let w = weights[to].entry(from).or_default();
+                    *w = *w + weight;
+                }
+            }
+        }
+
+        // Each remaining node may stand for several original nodes that have been merged.
+        let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+        let mut active: Vec<usize> = (0..n).collect();
+        let mut best: Option<(E, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // Find the most tightly connected ordering of the active nodes. The last two nodes
+            // in this ordering are `s` and `t`.
+            let mut added = vec![false; n];
+            let mut connectivity = vec![E::default(); n];
+            let mut heap: BinaryHeap<(E, usize)> =
+                active.iter().map(|&v| (E::default(), v)).collect();
+            let (mut s, mut t) = (usize::MAX, usize::MAX);
+            let mut cut_of_phase = E::default();
+            while let Some((w, v)) = heap.pop() {
+                if added[v] {
+                    continue;
+                }
+
+                added[v] = true;
+                (s, t) = (t, v);
+                cut_of_phase = w;
+                for (&u, &edge) in &weights[v] {
+                    if !added[u] {
+                        connectivity[u] = connectivity[u] + edge;
+                        heap.push((connectivity[u], u));
+                    }
+                }
+            }
+
+            if best.as_ref().is_none_or(|(b, _)| cut_of_phase < *b) {
+                best = Some((cut_of_phase, members[t].clone()));
+            }
+
+            // Merge `t` into `s`.
+            let merged = std::mem::take(&mut members[t]);
+            members[s].extend(merged);
+            for (u, edge) in std::mem::take(&mut weights[t]) {
+                weights[u].remove(&t);
+                if u == s {
+                    continue;
+                }
+
+                let w = weights[s].entry(u).or_default();
+                *w = *w + edge;
+                let w = weights[u].entry(s).or_default();
+                *w = *w + edge;
+            }
+
+            active.retain(|&v| v != t);
+        }
+
+        best.map(|(weight, mut side)| {
+            side.sort();
+            (weight, side.into_iter().map(NodeId).collect())
+        })
+    }
+}
+
+fn bron_kerbosch(
+    neighbors: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    cliques: &mut Vec<Vec<NodeId>>,
+) {
+    // Pick the pivot with the most candidate neighbors, since none of those neighbors need to
+    // be tried directly.
+    let Some(&pivot) = candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|&&u| neighbors[u].intersection(&candidates).count())
+    else {
+        let mut found = clique.iter().map(|&i| NodeId(i)).collect::<Vec<_>>();
+        found.sort();
+        cliques.push(found);
+        return;
+    };
+
+    let to_try = candidates
+        .difference(&neighbors[pivot])
+        .copied()
+        .collect::<Vec<_>>();
+    for v in to_try {
+        clique.push(v);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            candidates.intersection(&neighbors[v]).copied().collect(),
+            excluded.intersection(&neighbors[v]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+/// Answers lowest common ancestor queries on a rooted tree using binary lifting. After
+/// `O(n log n)` preprocessing, each query takes `O(log n)` time.
+#[derive(Clone, Debug)]
+pub struct LowestCommonAncestor {
+    /// The depth of each node, indexed by ID, or `None` if the node isn't in the tree.
+    depth: Vec<Option<usize>>,
+    /// `ancestors[k][v]` is the `2^k`-th ancestor of node `v`. The root is its own ancestor.
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl LowestCommonAncestor {
+    /// Prepares lowest common ancestor queries for the tree rooted at the given node. The tree
+    /// consists of every node reachable from `root`; if the graph has cycles, the tree found by
+    /// a breadth-first search is used.
+    ///
+    /// # Parameters
+    /// - `graph`: The graph containing the tree.
+    /// - `root`: The root of the tree.
+    ///
+    /// # Returns
+    /// The structure used to answer queries.
+    pub fn new<N, E>(graph: &Graph<N, E>, root: NodeId) -> Self {
+        let n = graph.node_count();
+        let mut depth = vec![None; n];
+        let mut parent = (0..n).collect::<Vec<_>>();
+        depth[root.0] = Some(0);
+        let mut queue = VecDeque::from([root.0]);
+        while let Some(v) = queue.pop_front() {
+            let d = depth[v].unwrap();
+            for &(NodeId(w), _) in &graph.edges[v] {
+                if depth[w].is_none() {
+                    depth[w] = Some(d + 1);
+                    parent[w] = v;
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut ancestors = vec![parent];
+        for k in 1..levels {
+            let prev = &ancestors[k - 1];
+            let next = (0..n).map(|v| prev[prev[v]]).collect();
+            ancestors.push(next);
+        }
+
+        LowestCommonAncestor { depth, ancestors }
+    }
+
+    /// Gets the depth of the given node, i.e., its distance from the root.
+    ///
+    /// # Parameters
+    /// - `node`: The node.
+    ///
+    /// # Returns
+    /// The depth, or `None` if the node isn't in the tree.
+    pub fn depth(&self, node: NodeId) -> Option<usize> {
+        self.depth[node.0]
+    }
+
+    /// Gets the parent of the given node.
+    ///
+    /// # Parameters
+    /// - `node`: The node.
+    ///
+    /// # Returns
+    /// The parent, or `None` if the node is the root or isn't in the tree.
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        match self.depth[node.0] {
+            Some(d) if d > 0 => Some(NodeId(self.ancestors[0][node.0])),
+            _ => None,
+        }
+    }
+
+    /// Finds the lowest common ancestor of two nodes, i.e., the deepest node that has both
+    /// nodes as descendants. A node counts as its own descendant.
+    ///
+    /// # Parameters
+    /// - `a`: The first node.
+    /// - `b`: The second node.
+    ///
+    /// # Returns
+    /// The lowest common ancestor, or `None` if either node isn't in the tree.
+    pub fn lca(&self, a: NodeId, b: NodeId) -> Option<NodeId> {
+        let (mut a, mut b) = (a.0, b.0);
+        let (mut depth_a, mut depth_b) = (self.depth[a]?, self.depth[b]?);
+        if depth_a < depth_b {
+            std::mem::swap(&mut a, &mut b);
+            std::mem::swap(&mut depth_a, &mut depth_b);
+        }
+
+        // Lift `a` up to the same depth as `b`.
+        let diff = depth_a - depth_b;
+        for (k, ancestors) in self.ancestors.iter().enumerate() {
+            if diff & (1 << k) != 0 {
+                a = ancestors[a];
+            }
+        }
+
+        if a == b {
+            return Some(NodeId(a));
+        }
+
+        // Lift both nodes as high as possible while keeping them apart; their parents are
+        // then the lowest common ancestor.
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[a] != ancestors[b] {
+                a = ancestors[a];
+                b = ancestors[b];
+            }
+        }
+
+        Some(NodeId(self.ancestors[0][a]))
+    }
+
+    /// Gets the number of edges on the path between two nodes in the tree.
+    ///
+    /// # Parameters
+    /// - `a`: The first node.
+    /// - `b`: The second node.
+    ///
+    /// # Returns
+    /// The distance, or `None` if either node isn't in the tree.
+    pub fn distance(&self, a: NodeId, b: NodeId) -> Option<usize> {
+        let ancestor = self.lca(a, b)?;
+        Some(self.depth[a.0]? + self.depth[b.0]? - 2 * self.depth[ancestor.0]?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Graph, LowestCommonAncestor, NodeId};
+
+    fn names(graph: &Graph<(), usize>, ids: &[NodeId]) -> Vec<String> {
+        let mut names = ids
+            .iter()
+            .map(|&id| graph.name(id).to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_interning() {
+        let mut graph: Graph<usize, ()> = Graph::new_undirected();
+        let a = graph.add_node("a", 5);
+        let (b, a2) = graph.add_edge_by_name("b", "a", ());
+        assert_eq!(a, a2);
+        assert_eq!(Some(b), graph.node_id("b"));
+        assert_eq!(None, graph.node_id("c"));
+        assert_eq!(0, *graph.data(b));
+        assert_eq!(5, *graph.data(a));
+        assert_eq!("b", graph.name(b));
+        assert_eq!(2, graph.node_count());
+        assert_eq!(1, graph.edge_count());
+        assert_eq!(
+            vec![b],
+            graph.neighbors(a).map(|(n, _)| n).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph: Graph<(), usize> = Graph::new_directed();
+        for (from, to) in [("a", "b"), ("c", "b"), ("d", "e"), ("f", "f")] {
+            graph.add_edge_by_name(from, to, 1);
+        }
+
+        let components = graph
+            .connected_components()
+            .iter()
+            .map(|c| names(&graph, c))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]],
+            components
+        );
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut graph: Graph<(), usize> = Graph::new_directed();
+        for (from, to) in [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("e", "f"),
+        ] {
+            graph.add_edge_by_name(from, to, 1);
+        }
+
+        let components = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|c| names(&graph, c))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![vec!["f"], vec!["d", "e"], vec!["a", "b", "c"]],
+            components
+        );
+    }
+
+    #[test]
+    fn test_simple_paths_caves() {
+        // The first example from 2021 day 12.
+        let mut graph: Graph<(), ()> = Graph::new_undirected();
+        for line in ["start-A", "start-b", "A-c", "A-b", "b-d", "A-end", "b-end"] {
+            let (from, to) = line.split_once('-').unwrap();
+            graph.add_edge_by_name(from, to, ());
+        }
+
+        let start = graph.node_id("start").unwrap();
+        let end = graph.node_id("end").unwrap();
+        let is_small = |id: NodeId| graph.name(id).chars().all(|c| c.is_lowercase());
+        let paths = graph.simple_paths(start, end, |id, visits| {
+            !is_small(id) || visits[id.index()] == 0
+        });
+        assert_eq!(10, paths.len());
+        assert!(paths
+            .iter()
+            .all(|p| p[0] == start && *p.last().unwrap() == end));
+
+        let twice = graph.count_simple_paths(start, end, |id, visits| {
+            if id == start {
+                return false;
+            }
+
+            !is_small(id)
+                || visits[id.index()] == 0
+                || graph
+                    .node_ids()
+                    .filter(|&n| is_small(n))
+                    .all(|n| visits[n.index()] < 2)
+        });
+        assert_eq!(36, twice);
+    }
+
+    #[test]
+    fn test_maximum_clique() {
+        let mut graph: Graph<(), ()> = Graph::new_undirected();
+        for (from, to) in [
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("b", "e"),
+            ("d", "e"),
+            ("f", "a"),
+        ] {
+            graph.add_edge_by_name(from, to, ());
+        }
+
+        let clique = graph.maximum_clique();
+        assert_eq!(3, clique.len());
+        assert_eq!(4, graph.maximal_cliques().len());
+
+        graph.add_edge_by_name("a", "d", ());
+        let clique = graph
+            .maximum_clique()
+            .iter()
+            .map(|&id| graph.name(id))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a", "b", "c", "d"], clique);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let mut graph: Graph<(), usize> = Graph::new_undirected();
+        for (from, to, w) in [
+            ("1", "2", 2),
+            ("1", "5", 3),
+            ("2", "3", 3),
+            ("2", "5", 2),
+            ("2", "6", 2),
+            ("3", "4", 4),
+            ("3", "7", 2),
+            ("4", "7", 2),
+            ("4", "8", 2),
+            ("5", "6", 3),
+            ("6", "7", 1),
+            ("7", "8", 3),
+        ] {
+            graph.add_edge_by_name(from, to, w);
+        }
+
+        let (weight, side) = graph.min_cut().unwrap();
+        assert_eq!(4, weight);
+        let side = names(&graph, &side);
+        assert!(side == ["1", "2", "5", "6"] || side == ["3", "4", "7", "8"]);
+
+        let single: Graph<(), usize> = Graph::new_undirected();
+        assert_eq!(None, single.min_cut());
+    }
+
+    #[test]
+    fn test_lowest_common_ancestor() {
+        // The example from 2019 day 6.
+        let mut graph: Graph<(), ()> = Graph::new_directed();
+        for orbit in [
+            "COM)B", "B)C", "C)D", "D)E", "E)F", "B)G", "G)H", "D)I", "E)J", "J)K", "K)L", "K)YOU",
+            "I)SAN",
+        ] {
+            let (center, object) = orbit.split_once(')').unwrap();
+            graph.add_edge_by_name(center, object, ());
+        }
+
+        let id = |name: &str| graph.node_id(name).unwrap();
+        let lca = LowestCommonAncestor::new(&graph, id("COM"));
+        assert_eq!(Some(id("D")), lca.lca(id("YOU"), id("SAN")));
+        assert_eq!(Some(id("B")), lca.lca(id("H"), id("F")));
+        assert_eq!(Some(id("E")), lca.lca(id("E"), id("L")));
+        assert_eq!(Some(id("K")), lca.parent(id("YOU")));
+        assert_eq!(None, lca.parent(id("COM")));
+        assert_eq!(Some(7), lca.depth(id("YOU")));
+        assert_eq!(Some(6), lca.distance(id("YOU"), id("SAN")));
+    }
+}
@@ -0,0 +1,729 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::Add;
+
+mod graph;
+pub use graph::{Graph, LowestCommonAncestor, NodeId};
+
+/// A cycle found in a graph that was expected to be acyclic.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cycle<E> {
+    /// The nodes in the cycle, in the order that the edges visit them. The last node has an
+    /// edge back to the first node.
+    nodes: Vec<E>,
+}
+
+impl<E> Cycle<E> {
+    /// Gets the nodes in the cycle.
+    ///
+    /// # Returns
+    /// The nodes in the cycle, where each node has an edge to the next node and the last node
+    /// has an edge back to the first node.
+    pub fn nodes(&self) -> &[E] {
+        &self.nodes
+    }
+}
+
+impl<E: Display> Display for Cycle<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("cycle: ")?;
+        for node in &self.nodes {
+            write!(f, "{node} -> ")?;
+        }
+
+        match self.nodes.first() {
+            Some(first) => write!(f, "{first}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<E: Display + Debug> Error for Cycle<E> {}
+
+/// Performs a topological sort on the given directed graph.
+///
+/// Nodes that only appear as neighbors (i.e., nodes that aren't keys of `graph`) are treated
+/// as nodes without any outgoing edges, and are included in the ordering. Note that the order
+/// in which independent nodes appear depends on the iteration order of `graph`; use
+/// [`topological_sort_by_key`] if a deterministic ordering is needed.
+///
+/// # Parameters
+/// - `graph`: The graph. This must be directed.
+///
+/// # Returns
+/// The topological ordering of the nodes, where every node appears before all of its
+/// neighbors. If the graph has a cycle, one such cycle is returned instead.
+pub fn topological_sort<E>(graph: &HashMap<E, Vec<E>>) -> Result<Vec<&E>, Cycle<E>>
+where
+    E: Hash + Eq + Clone,
+{
+    #[derive(PartialEq, Eq)]
+    enum VisitState {
+        OnStack,
+        Done,
+    }
+
+    let mut state: HashMap<&E, VisitState> = HashMap::new();
+    let mut postorder = Vec::with_capacity(graph.len());
+    for root in graph.keys() {
+        if state.contains_key(root) {
+            continue;
+        }
+
+        // An explicit stack of (node, index of the next neighbor to explore) is used instead
+        // of recursion so that long chains can't overflow the call stack.
+        state.insert(root, VisitState::OnStack);
+        let mut stack = vec![(root, 0)];
+        while let Some(&(node, idx)) = stack.last() {
+            let Some(next) = graph.get(node).and_then(|n| n.get(idx)) else {
+                state.insert(node, VisitState::Done);
+                postorder.push(node);
+                stack.pop();
+                continue;
+            };
+
+            stack.last_mut().unwrap().1 += 1;
+            match state.get(next) {
+                None => {
+                    state.insert(next, VisitState::OnStack);
+                    stack.push((next, 0));
+                }
+                Some(VisitState::OnStack) => {
+                    let start = stack.iter().position(|(n, _)| *n == next).unwrap();
+                    return Err(Cycle {
+                        nodes: stack[start..].iter().map(|(n, _)| (*n).clone()).collect(),
+                    });
+                }
+                Some(VisitState::Done) => {}
+            }
+        }
+    }
+
+    postorder.reverse();
+    Ok(postorder)
+}
+
+/// Performs a topological sort on the given directed graph, breaking ties deterministically.
+/// Whenever several nodes could come next, the one with the smallest key is chosen; this makes
+/// the ordering unique, which matters for puzzles that only define a partial ordering.
+///
+/// # Parameters
+/// - `graph`: The graph. This must be directed.
+/// - `key`: A function that computes the priority of a node. Smaller keys are placed first.
+///
+/// # Returns
+/// The topological ordering of the nodes, where every node appears before all of its
+/// neighbors. If the graph has a cycle, one such cycle is returned instead.
+pub fn topological_sort_by_key<E, K, F>(
+    graph: &HashMap<E, Vec<E>>,
+    mut key: F,
+) -> Result<Vec<&E>, Cycle<E>>
+where
+    E: Hash + Eq + Clone,
+    K: Ord,
+    F: FnMut(&E) -> K,
+{
+    let mut in_degree = in_degrees(graph);
+    // The heap orders (key, index) pairs, so the node type itself doesn't need to be `Ord`.
+    let mut nodes = vec![];
+    let mut heap = BinaryHeap::new();
+    for (&node, _) in in_degree.iter().filter(|(_, &d)| d == 0) {
+        heap.push(Reverse((key(node), nodes.len())));
+        nodes.push(node);
+    }
+
+    let mut ordering = Vec::with_capacity(in_degree.len());
+    while let Some(Reverse((_, idx))) = heap.pop() {
+        let node = nodes[idx];
+        ordering.push(node);
+        for next in graph.get(node).into_iter().flatten() {
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                heap.push(Reverse((key(next), nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+
+    if ordering.len() == in_degree.len() {
+        Ok(ordering)
+    } else {
+        Err(find_cycle(graph, &in_degree))
+    }
+}
+
+/// Creates an iterator that yields the nodes of the given directed graph in topological order,
+/// using Kahn's algorithm. Nodes are yielded lazily, so this can be used to process a graph
+/// one "ready" node at a time.
+///
+/// # Parameters
+/// - `graph`: The graph. This must be directed.
+///
+/// # Returns
+/// The iterator. If the graph has a cycle, the iterator stops early, and
+/// [`TopologicalIter::remaining_cycle`] can be used to find out why.
+pub fn topological_iter<E>(graph: &HashMap<E, Vec<E>>) -> TopologicalIter<'_, E>
+where
+    E: Hash + Eq,
+{
+    let in_degree = in_degrees(graph);
+    let queue = in_degree
+        .iter()
+        .filter(|(_, &d)| d == 0)
+        .map(|(&n, _)| n)
+        .collect();
+    TopologicalIter {
+        graph,
+        in_degree,
+        queue,
+    }
+}
+
+/// An iterator over the nodes of a directed graph in topological order. See
+/// [`topological_iter`].
+pub struct TopologicalIter<'a, E> {
+    /// The graph.
+    graph: &'a HashMap<E, Vec<E>>,
+    /// The number of incoming edges from nodes that haven't been yielded yet.
+    in_degree: HashMap<&'a E, usize>,
+    /// The nodes that have no remaining incoming edges, but haven't been yielded yet.
+    queue: VecDeque<&'a E>,
+}
+
+impl<E> TopologicalIter<'_, E>
+where
+    E: Hash + Eq + Clone,
+{
+    /// Finds the cycle that prevents the remaining nodes from being yielded. This should be
+    /// called once the iterator is exhausted.
+    ///
+    /// # Returns
+    /// A cycle, if the iterator stopped before every node was yielded. Otherwise, `None`.
+    pub fn remaining_cycle(&self) -> Option<Cycle<E>> {
+        if self.queue.is_empty() && self.in_degree.values().any(|&d| d > 0) {
+            Some(find_cycle(self.graph, &self.in_degree))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, E> Iterator for TopologicalIter<'a, E>
+where
+    E: Hash + Eq,
+{
+    type Item = &'a E;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        for next in self.graph.get(node).into_iter().flatten() {
+            let degree = self.in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                self.queue.push_back(next);
+            }
+        }
+
+        Some(node)
+    }
+}
+
+/// Computes the in-degree of every node in the graph, including nodes that only appear as
+/// neighbors.
+///
+/// # Parameters
+/// - `graph`: The graph.
+///
+/// # Returns
+/// A map from each node to the number of edges pointing to it.
+fn in_degrees<E>(graph: &HashMap<E, Vec<E>>) -> HashMap<&E, usize>
+where
+    E: Hash + Eq,
+{
+    let mut in_degree: HashMap<&E, usize> = graph.keys().map(|n| (n, 0)).collect();
+    for next in graph.values().flatten() {
+        *in_degree.entry(next).or_insert(0) += 1;
+    }
+
+    in_degree
+}
+
+/// Finds a cycle among the nodes that Kahn's algorithm couldn't process. Every such node still
+/// has an incoming edge from another unprocessed node, so repeatedly stepping to one of those
+/// predecessors must eventually revisit a node.
+///
+/// # Parameters
+/// - `graph`: The graph.
+/// - `in_degree`: The remaining in-degrees after running Kahn's algorithm.
+///
+/// # Returns
+/// A cycle among the unprocessed nodes.
+fn find_cycle<E>(graph: &HashMap<E, Vec<E>>, in_degree: &HashMap<&E, usize>) -> Cycle<E>
+where
+    E: Hash + Eq + Clone,
+{
+    let is_remaining = |n: &E| in_degree.get(n).is_some_and(|&d| d > 0);
+    let mut predecessor: HashMap<&E, &E> = HashMap::new();
+    for (node, neighbors) in graph.iter().filter(|(n, _)| is_remaining(n)) {
+        for next in neighbors.iter().filter(|n| is_remaining(n)) {
+            predecessor.entry(next).or_insert(node);
+        }
+    }
+
+    let mut curr = *predecessor.keys().next().expect("no unprocessed nodes");
+    let mut seen = HashMap::new();
+    let mut walk = vec![];
+    while !seen.contains_key(curr) {
+        seen.insert(curr, walk.len());
+        walk.push(curr);
+        curr = predecessor[curr];
+    }
+
+    // The walk follows edges backwards, so the cycle needs to be reversed.
+    let mut nodes: Vec<E> = walk[seen[curr]..].iter().map(|&n| n.clone()).collect();
+    nodes.reverse();
+    Cycle { nodes }
+}
+
+/// The result of running a shortest-path search from a single source node.
+///
+/// The search records, for every reached node, the cost of the cheapest way to reach it along
+/// with the predecessor(s) that achieve that cost. If the search was told to record all
+/// predecessors, every equal-cost predecessor is kept, so that all shortest paths (rather than
+/// just one) can be recovered without storing a history for each explored state.
+pub struct ShortestPaths<N, C> {
+    /// The node that the search started from.
+    start: N,
+    /// The cheapest known cost to reach each node.
+    costs: HashMap<N, C>,
+    /// The predecessors of each node along the cheapest paths to it.
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N, C> ShortestPaths<N, C>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord,
+{
+    /// Gets the node that the search started from.
+    ///
+    /// # Returns
+    /// The starting node.
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// Gets the cost of the cheapest path to the given node.
+    ///
+    /// # Parameters
+    /// - `node`: The node.
+    ///
+    /// # Returns
+    /// The cost of the cheapest path to the node, or `None` if the node was never reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// Gets the predecessors of the given node along the cheapest paths to it.
+    ///
+    /// # Parameters
+    /// - `node`: The node.
+    ///
+    /// # Returns
+    /// The predecessors. This is empty for the starting node and for unreached nodes.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], |p| p.as_slice())
+    }
+
+    /// Finds the cheapest cost among the given targets, along with the targets achieving it.
+    ///
+    /// # Parameters
+    /// - `targets`: The target nodes.
+    ///
+    /// # Returns
+    /// The cheapest cost and every reached target that has that cost, or `None` if no target
+    /// was reached.
+    fn best_targets<'a>(&self, targets: &'a [N]) -> Option<(C, Vec<&'a N>)> {
+        let best = targets.iter().filter_map(|t| self.cost(t)).min()?;
+        Some((
+            best,
+            targets
+                .iter()
+                .filter(|t| self.cost(t) == Some(best))
+                .collect(),
+        ))
+    }
+
+    /// Reconstructs one cheapest path from the starting node to the given node.
+    ///
+    /// # Parameters
+    /// - `target`: The node to reconstruct the path to.
+    ///
+    /// # Returns
+    /// The path, starting with the starting node and ending with `target`, or `None` if the
+    /// target was never reached.
+    pub fn path_to(&self, target: &N) -> Option<Vec<N>> {
+        self.costs.get(target)?;
+        let mut path = vec![target.clone()];
+        let mut curr = target;
+        while let Some(prev) = self.predecessors(curr).first() {
+            path.push(prev.clone());
+            curr = prev;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Reconstructs every cheapest path from the starting node to the given node.
+    ///
+    /// Note that the number of shortest paths can grow exponentially with the size of the
+    /// graph; if you only need to know which nodes lie on a shortest path, use
+    /// [`ShortestPaths::nodes_on_paths_to`] instead.
+    ///
+    /// # Parameters
+    /// - `target`: The node to reconstruct the paths to.
+    ///
+    /// # Returns
+    /// All paths, each starting with the starting node and ending with `target`. This is empty
+    /// if the target was never reached. Only one path is returned if the search did not record
+    /// all predecessors.
+    pub fn all_paths_to(&self, target: &N) -> Vec<Vec<N>> {
+        let mut paths = vec![];
+        if self.costs.contains_key(target) {
+            let mut reversed_path = vec![target.clone()];
+            self.collect_paths(target, &mut reversed_path, &mut paths);
+        }
+
+        paths
+    }
+
+    fn collect_paths(&self, node: &N, reversed_path: &mut Vec<N>, paths: &mut Vec<Vec<N>>) {
+        let preds = self.predecessors(node);
+        if preds.is_empty() {
+            paths.push(reversed_path.iter().rev().cloned().collect());
+            return;
+        }
+
+        for pred in preds {
+            reversed_path.push(pred.clone());
+            self.collect_paths(pred, reversed_path, paths);
+            reversed_path.pop();
+        }
+    }
+
+    /// Finds every node that lies on at least one cheapest path from the starting node to any
+    /// of the given targets. Only the targets with the cheapest cost among `targets` are
+    /// considered, which is useful when several nodes represent the same goal (e.g., the same
+    /// grid position entered from different directions).
+    ///
+    /// # Parameters
+    /// - `targets`: The target nodes.
+    ///
+    /// # Returns
+    /// The set of nodes, including the starting node and the cheapest targets. This is empty if
+    /// no target was reached.
+    pub fn nodes_on_paths_to(&self, targets: &[N]) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let Some((_, best)) = self.best_targets(targets) else {
+            return seen;
+        };
+
+        let mut queue = VecDeque::new();
+        for target in best {
+            if seen.insert(target.clone()) {
+                queue.push_back(target);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            for pred in self.predecessors(node) {
+                if seen.insert(pred.clone()) {
+                    queue.push_back(pred);
+                }
+            }
+        }
+
+        seen
+    }
+}
+
+/// Runs Dijkstra's algorithm from the given starting node, exploring the entire reachable graph.
+///
+/// # Parameters
+/// - `start`: The starting node.
+/// - `neighbors`: A function that, given a node, returns its neighbors along with the
+///   (non-negative) cost of moving to each neighbor.
+/// - `record_all_predecessors`: Whether to record every predecessor that achieves the cheapest
+///   cost to a node. If `false`, only the first such predecessor is recorded, which is enough
+///   to reconstruct a single shortest path. If `true`, there should be no cycles of zero-cost
+///   edges, since such cycles would make the number of shortest paths infinite.
+///
+/// # Returns
+/// The costs and predecessors of every reachable node.
+pub fn dijkstra<N, C, F, I>(
+    start: N,
+    neighbors: F,
+    record_all_predecessors: bool,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    dijkstra_until(start, neighbors, |_| false, record_all_predecessors)
+}
+
+/// Runs Dijkstra's algorithm from the given starting node, stopping once every node that is as
+/// cheap to reach as the first goal node has been settled. Because equal-cost nodes are still
+/// settled, all cheapest paths to the goal are recorded when `record_all_predecessors` is set.
+///
+/// # Parameters
+/// - `start`: The starting node.
+/// - `neighbors`: A function that, given a node, returns its neighbors along with the
+///   (non-negative) cost of moving to each neighbor.
+/// - `is_goal`: A function that determines whether a node is a goal.
+/// - `record_all_predecessors`: Whether to record every predecessor that achieves the cheapest
+///   cost to a node.
+///
+/// # Returns
+/// The costs and predecessors of every node settled by the search.
+pub fn dijkstra_until<N, C, F, I, G>(
+    start: N,
+    mut neighbors: F,
+    mut is_goal: G,
+    record_all_predecessors: bool,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    // Nodes are interned so that the heap only has to order (cost, index) pairs, which means
+    // that the node type itself does not need to implement `Ord`.
+    let mut nodes: Vec<N> = vec![start.clone()];
+    let mut ids: HashMap<N, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut goal_cost = None;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((C::default(), 0)));
+    while let Some(Reverse((cost, id))) = heap.pop() {
+        if goal_cost.is_some_and(|g| cost > g) {
+            break;
+        }
+
+        if !settled.insert(id) {
+            continue;
+        }
+
+        let node = nodes[id].clone();
+        if goal_cost.is_none() && is_goal(&node) {
+            goal_cost = Some(cost);
+        }
+
+        for (next, step_cost) in neighbors(&node) {
+            let next_cost = cost + step_cost;
+            match costs.get(&next).map(|c| next_cost.cmp(c)) {
+                Some(Ordering::Greater) => continue,
+                Some(Ordering::Equal) => {
+                    if record_all_predecessors {
+                        let preds = predecessors.entry(next).or_default();
+                        if !preds.contains(&node) {
+                            preds.push(node.clone());
+                        }
+                    }
+
+                    continue;
+                }
+                Some(Ordering::Less) | None => {}
+            }
+
+            let next_id = *ids.entry(next.clone()).or_insert_with(|| {
+                nodes.push(next.clone());
+                nodes.len() - 1
+            });
+
+            costs.insert(next.clone(), next_cost);
+            predecessors.insert(next, vec![node.clone()]);
+            heap.push(Reverse((next_cost, next_id)));
+        }
+    }
+
+    // If the search stopped early, some costs are only tentative, so they shouldn't be reported.
+    costs.retain(|node, _| settled.contains(&ids[node]));
+    predecessors.retain(|node, _| costs.contains_key(node));
+
+    ShortestPaths {
+        start,
+        costs,
+        predecessors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        dijkstra, dijkstra_until, topological_iter, topological_sort, topological_sort_by_key,
+    };
+    use std::collections::{HashMap, HashSet};
+
+    /// Checks that every node in `ordering` appears before all of its neighbors.
+    fn assert_topological(graph: &HashMap<u32, Vec<u32>>, ordering: &[&u32]) {
+        let position: HashMap<u32, usize> =
+            ordering.iter().enumerate().map(|(i, &&n)| (n, i)).collect();
+        for (node, neighbors) in graph {
+            for next in neighbors {
+                assert!(position[node] < position[next], "{node} -> {next}");
+            }
+        }
+    }
+
+    fn dag() -> HashMap<u32, Vec<u32>> {
+        HashMap::from([
+            (5, vec![11]),
+            (7, vec![11, 8]),
+            (3, vec![8, 10]),
+            (11, vec![2, 9, 10]),
+            (8, vec![9]),
+        ])
+    }
+
+    #[test]
+    fn test_topological_sort_dag() {
+        let graph = dag();
+        let ordering = topological_sort(&graph).unwrap();
+        // 2, 9 and 10 only appear as neighbors.
+        assert_eq!(8, ordering.len());
+        assert_topological(&graph, &ordering);
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let graph = HashMap::from([(1, vec![2]), (2, vec![3]), (3, vec![4, 1]), (0, vec![1])]);
+        let cycle = topological_sort(&graph).unwrap_err();
+        let mut nodes = cycle.nodes().to_vec();
+        nodes.sort();
+        assert_eq!(vec![1, 2, 3], nodes);
+
+        let self_loop = HashMap::from([(1, vec![1])]);
+        assert_eq!([1], topological_sort(&self_loop).unwrap_err().nodes());
+    }
+
+    #[test]
+    fn test_topological_sort_by_key() {
+        let graph = dag();
+        let ordering = topological_sort_by_key(&graph, |&n| n).unwrap();
+        assert_eq!(vec![&3, &5, &7, &8, &11, &2, &9, &10], ordering);
+
+        let reversed = topological_sort_by_key(&graph, |&n| u32::MAX - n).unwrap();
+        assert_eq!(vec![&7, &5, &11, &3, &10, &8, &9, &2], reversed);
+    }
+
+    #[test]
+    fn test_topological_sort_by_key_cycle() {
+        let graph = HashMap::from([(0, vec![1]), (1, vec![2]), (2, vec![3, 1]), (3, vec![])]);
+        let cycle = topological_sort_by_key(&graph, |&n| n).unwrap_err();
+        let nodes = cycle.nodes();
+        assert_eq!(2, nodes.len());
+        assert!(nodes.contains(&1) && nodes.contains(&2));
+
+        let self_loop = HashMap::from([(1, vec![1])]);
+        let cycle = topological_sort_by_key(&self_loop, |&n| n).unwrap_err();
+        assert_eq!("cycle: 1 -> 1", cycle.to_string());
+    }
+
+    #[test]
+    fn test_topological_iter() {
+        let graph = dag();
+        let mut iter = topological_iter(&graph);
+        let ordering = iter.by_ref().collect::<Vec<_>>();
+        assert_eq!(8, ordering.len());
+        assert_topological(&graph, &ordering);
+        assert!(iter.remaining_cycle().is_none());
+
+        let cyclic = HashMap::from([(0, vec![1]), (1, vec![2]), (2, vec![1, 3])]);
+        let mut iter = topological_iter(&cyclic);
+        assert_eq!(vec![&0], iter.by_ref().collect::<Vec<_>>());
+        let mut nodes = iter.remaining_cycle().unwrap().nodes().to_vec();
+        nodes.sort();
+        assert_eq!(vec![1, 2], nodes);
+    }
+
+    fn diamond() -> HashMap<char, Vec<(char, usize)>> {
+        HashMap::from([
+            ('A', vec![('B', 1), ('C', 1), ('E', 5)]),
+            ('B', vec![('D', 1)]),
+            ('C', vec![('D', 1)]),
+            ('D', vec![('E', 1)]),
+            ('F', vec![('A', 1)]),
+        ])
+    }
+
+    #[test]
+    fn test_dijkstra_costs() {
+        let graph = diamond();
+        let paths = dijkstra('A', |n| graph.get(n).cloned().unwrap_or_default(), false);
+        assert_eq!(Some(0), paths.cost(&'A'));
+        assert_eq!(Some(2), paths.cost(&'D'));
+        assert_eq!(Some(3), paths.cost(&'E'));
+        assert_eq!(None, paths.cost(&'F'));
+        assert_eq!(None, paths.path_to(&'F'));
+    }
+
+    #[test]
+    fn test_dijkstra_single_path() {
+        let graph = diamond();
+        let paths = dijkstra('A', |n| graph.get(n).cloned().unwrap_or_default(), false);
+        let path = paths.path_to(&'E').unwrap();
+        assert_eq!(4, path.len());
+        assert_eq!('A', path[0]);
+        assert_eq!('E', path[3]);
+        assert_eq!(1, paths.all_paths_to(&'E').len());
+        assert_eq!(vec![vec!['A']], paths.all_paths_to(&'A'));
+    }
+
+    #[test]
+    fn test_dijkstra_all_paths() {
+        let graph = diamond();
+        let paths = dijkstra('A', |n| graph.get(n).cloned().unwrap_or_default(), true);
+        let mut all = paths.all_paths_to(&'E');
+        all.sort();
+        assert_eq!(
+            vec![vec!['A', 'B', 'D', 'E'], vec!['A', 'C', 'D', 'E']],
+            all
+        );
+        assert_eq!(
+            HashSet::from(['A', 'B', 'C', 'D', 'E']),
+            paths.nodes_on_paths_to(&['E'])
+        );
+        assert_eq!(
+            HashSet::from(['A', 'B']),
+            paths.nodes_on_paths_to(&['B', 'D', 'F'])
+        );
+        assert!(paths.nodes_on_paths_to(&['F']).is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra_until_goal() {
+        let graph = diamond();
+        let paths = dijkstra_until(
+            'A',
+            |n| graph.get(n).cloned().unwrap_or_default(),
+            |n| *n == 'D',
+            true,
+        );
+        assert_eq!(Some(2), paths.cost(&'D'));
+        assert_eq!(2, paths.all_paths_to(&'D').len());
+        assert_eq!(None, paths.cost(&'E'));
+    }
+}
@@ -0,0 +1,250 @@
+use super::{IntCodeComputer, IntcodeError, RunState};
+use std::io::{self, BufRead, Write};
+
+/// The output of an ASCII-speaking Intcode program, collected until it stopped.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct AsciiOutput {
+    /// The text that the program printed. Values outside of the ASCII range that weren't the
+    /// last output are written out as decimal numbers.
+    text: String,
+    /// The last output, if it was outside of the ASCII range.
+    answer: Option<isize>,
+    /// Why the program stopped.
+    state: RunState,
+}
+
+impl AsciiOutput {
+    /// Creates the output from the raw values that the program produced.
+    ///
+    /// # Parameters
+    /// - `values`: The values.
+    /// - `state`: Why the program stopped.
+    ///
+    /// # Returns
+    /// The output.
+    fn new(mut values: Vec<isize>, state: RunState) -> Self {
+        let answer = values.pop_if(|v| !is_ascii(*v));
+        let mut text = String::with_capacity(values.len());
+        for value in values {
+            if is_ascii(value) {
+                text.push(value as u8 as char);
+            } else {
+                text.push_str(&value.to_string());
+            }
+        }
+
+        Self {
+            text,
+            answer,
+            state,
+        }
+    }
+
+    /// Gets the text that the program printed.
+    ///
+    /// # Returns
+    /// The text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Gets the numeric answer that the program printed, i.e., a last output that's too large
+    /// (or negative) to be an ASCII character.
+    ///
+    /// # Returns
+    /// The answer, or `None` if the last output was ASCII.
+    pub fn answer(&self) -> Option<isize> {
+        self.answer
+    }
+
+    /// Gets the reason that the program stopped, usually [`RunState::NeedsInput`] when it's
+    /// prompting for the next line or [`RunState::Halted`].
+    ///
+    /// # Returns
+    /// Why the program stopped.
+    pub fn state(&self) -> RunState {
+        self.state
+    }
+}
+
+impl IntCodeComputer {
+    /// Inputs each character of the string, in order, to the computer's input queue.
+    ///
+    /// # Parameters
+    /// - `text`: The text. This should only contain ASCII characters.
+    pub fn input_str(&mut self, text: &str) {
+        for b in text.bytes() {
+            self.input_to_stdin(b as isize);
+        }
+    }
+
+    /// Inputs the string followed by a newline to the computer's input queue.
+    ///
+    /// # Parameters
+    /// - `line`: The line, without a trailing newline.
+    pub fn input_line(&mut self, line: &str) {
+        self.input_str(line);
+        self.input_to_stdin(b'\n' as isize);
+    }
+
+    /// Runs the program until it needs input that isn't available or halts, collecting its
+    /// output as text.
+    ///
+    /// # Returns
+    /// The output produced during this run.
+    ///
+    /// # Panics
+    /// If the program can't be executed. Use [`IntCodeComputer::try_run_ascii`] to handle this
+    /// case.
+    pub fn run_ascii(&mut self) -> AsciiOutput {
+        self.try_run_ascii().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Runs the program until it needs input that isn't available or halts, collecting its
+    /// output as text. This is like [`IntCodeComputer::run_ascii`], but returns an error
+    /// instead of panicking if the program can't be executed.
+    ///
+    /// # Returns
+    /// The output produced during this run, or the error that stopped the program.
+    pub fn try_run_ascii(&mut self) -> Result<AsciiOutput, IntcodeError> {
+        let mut values = vec![];
+        let state = loop {
+            match self.try_run()? {
+                RunState::Output(value) => values.push(value),
+                state => break state,
+            }
+        };
+
+        Ok(AsciiOutput::new(values, state))
+    }
+
+    /// Runs the program interactively, writing its text to `output` and answering each prompt
+    /// for input with a line read from `input`. Any numeric answer is written on its own line.
+    ///
+    /// # Parameters
+    /// - `input`: Where to read lines of input from, e.g., the standard input.
+    /// - `output`: Where to write the program's text, e.g., the standard output.
+    ///
+    /// # Returns
+    /// The last numeric answer that the program printed, if any, once the program halts or
+    /// `input` runs out of lines. If the program can't be executed, the [`IntcodeError`] is
+    /// returned as the source of an I/O error.
+    pub fn run_interactive<R, W>(
+        &mut self,
+        mut input: R,
+        mut output: W,
+    ) -> io::Result<Option<isize>>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut answer = None;
+        loop {
+            let result = self.try_run_ascii().map_err(io::Error::other)?;
+            write!(output, "{}", result.text())?;
+            if let Some(value) = result.answer() {
+                if !result.text().is_empty() && !result.text().ends_with('\n') {
+                    writeln!(output)?;
+                }
+
+                writeln!(output, "{value}")?;
+                answer = Some(value);
+            }
+
+            output.flush()?;
+            match result.state() {
+                RunState::Halted => return Ok(answer),
+                RunState::NeedsInput => {
+                    let mut line = String::new();
+                    if input.read_line(&mut line)? == 0 {
+                        return Ok(answer);
+                    }
+
+                    self.input_line(line.trim_end_matches(['\r', '\n']));
+                }
+                // Stopped at a breakpoint or watchpoint, so just keep going.
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Whether the value is an ASCII character.
+///
+/// # Parameters
+/// - `value`: The value.
+///
+/// # Returns
+/// `true` if the value is in `0..=127` and `false` otherwise.
+fn is_ascii(value: isize) -> bool {
+    (0..=127).contains(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::{assemble, IntCodeComputer, RunState};
+
+    /// Prints "Name?", echoes a line of input and then prints 12345.
+    fn program() -> Vec<isize> {
+        assemble(
+            "
+                ARB #msg
+            print: JF [rb], #read
+                OUT [rb]
+                ARB #1
+                JT #1, #print
+            read: IN -> [c]
+                OUT [c]
+                EQ [c], #10 -> [t]
+                JF [t], #read
+                OUT #12345
+                HALT
+            c: DATA 0
+            t: DATA 0
+            msg: DATA 78, 97, 109, 101, 63, 10, 0
+            ",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    pub fn test_run_ascii() {
+        let mut c = IntCodeComputer::new(&program(), None);
+        let output = c.run_ascii();
+        assert_eq!("Name?\n", output.text());
+        assert_eq!(None, output.answer());
+        assert_eq!(RunState::NeedsInput, output.state());
+
+        c.input_str("Bo");
+        c.input_line("b");
+        let output = c.run_ascii();
+        assert_eq!("Bob\n", output.text());
+        assert_eq!(Some(12345), output.answer());
+        assert_eq!(RunState::Halted, output.state());
+    }
+
+    #[test]
+    pub fn test_non_ascii_in_text() {
+        let program = assemble("OUT #65\nOUT #1000\nOUT #66\nOUT #-1\nHALT").unwrap();
+        let output = IntCodeComputer::new(&program, None).run_ascii();
+        assert_eq!("A1000B", output.text());
+        assert_eq!(Some(-1), output.answer());
+    }
+
+    #[test]
+    pub fn test_run_interactive() {
+        let mut c = IntCodeComputer::new(&program(), None);
+        let mut output = vec![];
+        let answer = c
+            .run_interactive("Bob\r\n".as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(Some(12345), answer);
+        assert_eq!("Name?\nBob\n12345\n", String::from_utf8(output).unwrap());
+
+        // Running out of input stops the program early.
+        let mut c = IntCodeComputer::new(&program(), None);
+        let mut output = vec![];
+        assert_eq!(None, c.run_interactive("".as_bytes(), &mut output).unwrap());
+        assert_eq!("Name?\n", String::from_utf8(output).unwrap());
+    }
+}
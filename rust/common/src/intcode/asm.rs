@@ -0,0 +1,386 @@
+use super::{get_args_needed, opcode_for_mnemonic, writes_to_memory};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error found while assembling an Intcode program.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct AssemblyError {
+    /// The line number (starting from 1) where the error was found.
+    line: usize,
+    /// A description of the error.
+    message: String,
+}
+
+impl AssemblyError {
+    /// Gets the line number where the error was found.
+    ///
+    /// # Returns
+    /// The line number, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets a description of the error.
+    ///
+    /// # Returns
+    /// The description.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for AssemblyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AssemblyError {}
+
+/// A value in the assembled program, which may need to wait until every label is known.
+enum AsmWord<'a> {
+    /// A value that's already known.
+    Value(isize),
+    /// An expression that may refer to labels.
+    Expression(&'a str),
+}
+
+/// Assembles a program written in Intcode assembly, the same language that
+/// [`super::disassemble`] produces. Each line holds at most one statement:
+/// - An instruction, like `ADD [rb+3], #5 -> [100]`. Operands are written as `#n` for
+///   immediate mode, `[n]` for position mode and `[rb+n]`, `[rb-n]` or `[rb]` for relative
+///   mode. The operand that an instruction writes to can optionally be separated with `->`
+///   instead of a comma.
+/// - A data directive, like `DATA 1, 2, 3`, which places the values as they are.
+///
+/// Statements can be preceded by labels like `loop:`, whose value is the address of the next
+/// statement. Values can be numbers, labels, or sums and differences of them, like `#end-1`
+/// or `[buffer+2]`. Everything after a `;` is a comment, and numeric prefixes like `0010:` are
+/// ignored, so a listing can be assembled back into the program it came from.
+///
+/// # Parameters
+/// - `source`: The assembly code.
+///
+/// # Returns
+/// The assembled program, or the first error found.
+pub fn assemble(source: &str) -> Result<Vec<isize>, AssemblyError> {
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut words: Vec<(usize, AsmWord)> = vec![];
+    for (idx, raw_line) in source.lines().enumerate() {
+        let line = idx + 1;
+        let error = |message: String| AssemblyError { line, message };
+        let mut text = raw_line.split(';').next().unwrap_or("").trim();
+
+        while let Some((head, tail)) = text.split_once(':') {
+            let head = head.trim();
+            if is_label(head) {
+                if labels.insert(head, words.len()).is_some() {
+                    return Err(error(format!("label \"{head}\" is already defined")));
+                }
+            } else if head.is_empty() || !head.chars().all(|c| c.is_ascii_digit()) {
+                return Err(error(format!("invalid label \"{head}\"")));
+            }
+
+            text = tail.trim();
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let (mnemonic, operands) = text
+            .split_once(char::is_whitespace)
+            .map(|(m, o)| (m, o.trim()))
+            .unwrap_or((text, ""));
+        let mnemonic = mnemonic.to_ascii_uppercase();
+        if mnemonic == "DATA" {
+            if operands.is_empty() {
+                return Err(error("DATA needs at least one value".to_string()));
+            }
+
+            for value in split_operands(operands).map_err(error)? {
+                words.push((line, AsmWord::Expression(value)));
+            }
+
+            continue;
+        }
+
+        let Some(opcode) = opcode_for_mnemonic(&mnemonic) else {
+            return Err(error(format!("unknown mnemonic \"{mnemonic}\"")));
+        };
+
+        let (reads, written) = match operands.split_once("->") {
+            Some((reads, written)) => (reads.trim(), Some(written.trim())),
+            None => (operands, None),
+        };
+
+        let mut parameters = split_operands(reads).map_err(error)?;
+        if let Some(written) = written {
+            if !writes_to_memory(opcode) {
+                return Err(error(format!("{mnemonic} doesn't write to memory")));
+            }
+
+            parameters.push(written);
+        }
+
+        let num_args = get_args_needed(opcode).unwrap();
+        if parameters.len() != num_args {
+            return Err(error(format!(
+                "{mnemonic} takes {num_args} operand(s), but {} were given",
+                parameters.len()
+            )));
+        }
+
+        let mut raw_opcode = opcode;
+        let mut place = 100;
+        let opcode_idx = words.len();
+        words.push((line, AsmWord::Value(0)));
+        for (i, parameter) in parameters.into_iter().enumerate() {
+            let (mode, expression) = parse_operand(parameter).map_err(error)?;
+            if mode == 1 && i + 1 == num_args && writes_to_memory(opcode) {
+                return Err(error(format!(
+                    "{mnemonic} can't write to an immediate operand"
+                )));
+            }
+
+            raw_opcode += mode * place;
+            place *= 10;
+            words.push((line, AsmWord::Expression(expression)));
+        }
+
+        words[opcode_idx].1 = AsmWord::Value(raw_opcode);
+    }
+
+    words
+        .into_iter()
+        .map(|(line, word)| match word {
+            AsmWord::Value(v) => Ok(v),
+            AsmWord::Expression(expression) => {
+                evaluate(expression, &labels).map_err(|message| AssemblyError { line, message })
+            }
+        })
+        .collect()
+}
+
+/// Whether the given text can be used as a label. Labels start with a letter or underscore,
+/// followed by letters, digits or underscores. `rb` is reserved for relative mode.
+///
+/// # Parameters
+/// - `text`: The text.
+///
+/// # Returns
+/// `true` if the text is a valid label and `false` otherwise.
+fn is_label(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && text != "rb"
+}
+
+/// Splits a comma-separated list of operands.
+///
+/// # Parameters
+/// - `operands`: The operands.
+///
+/// # Returns
+/// The trimmed operands, or an error if any of them is empty.
+fn split_operands(operands: &str) -> Result<Vec<&str>, String> {
+    if operands.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    operands
+        .split(',')
+        .map(|operand| match operand.trim() {
+            "" => Err(format!("missing operand in \"{operands}\"")),
+            operand => Ok(operand),
+        })
+        .collect()
+}
+
+/// Parses an operand into its mode and the expression for its value.
+///
+/// # Parameters
+/// - `operand`: The operand, like `#5`, `[100]` or `[rb-3]`.
+///
+/// # Returns
+/// A tuple where the first element is the mode digit of the operand and the second element is
+/// the expression for its value, or an error if the operand has no valid mode.
+fn parse_operand(operand: &str) -> Result<(isize, &str), String> {
+    if let Some(expression) = operand.strip_prefix('#') {
+        return Ok((1, expression));
+    }
+
+    let Some(inner) = operand
+        .strip_prefix('[')
+        .and_then(|o| o.strip_suffix(']'))
+        .map(str::trim)
+    else {
+        return Err(format!(
+            "operand \"{operand}\" should be written as #n, [n] or [rb+n]"
+        ));
+    };
+
+    match inner.strip_prefix("rb").map(str::trim_start) {
+        Some("") => Ok((2, "0")),
+        Some(offset) if offset.starts_with(['+', '-']) => Ok((2, offset)),
+        _ => Ok((0, inner)),
+    }
+}
+
+/// Evaluates an expression made of numbers and labels, joined by `+` or `-`. The expression
+/// may start with a sign.
+///
+/// # Parameters
+/// - `expression`: The expression.
+/// - `labels`: The address of each label.
+///
+/// # Returns
+/// The value of the expression, or an error if it's invalid or refers to an unknown label.
+fn evaluate(expression: &str, labels: &HashMap<&str, usize>) -> Result<isize, String> {
+    let invalid = || format!("invalid expression \"{expression}\"");
+    let mut rest = expression.trim();
+    let mut total: isize = 0;
+    let mut negative = false;
+    if let Some(r) = rest.strip_prefix('-') {
+        rest = r;
+        negative = true;
+    } else if let Some(r) = rest.strip_prefix('+') {
+        rest = r;
+    }
+
+    loop {
+        rest = rest.trim_start();
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let (term, after) = rest.split_at(end);
+        let value = if term.starts_with(|c: char| c.is_ascii_digit()) {
+            term.parse::<isize>()
+                .map_err(|_| format!("invalid number \"{term}\""))?
+        } else if let Some(&address) = labels.get(term) {
+            address as isize
+        } else if is_label(term) {
+            return Err(format!("unknown label \"{term}\""));
+        } else {
+            return Err(invalid());
+        };
+
+        total = if negative {
+            total.checked_sub(value)
+        } else {
+            total.checked_add(value)
+        }
+        .ok_or_else(|| format!("expression \"{expression}\" overflows"))?;
+
+        rest = after.trim_start();
+        match rest.chars().next() {
+            None => return Ok(total),
+            Some('+') => negative = false,
+            Some('-') => negative = true,
+            Some(_) => return Err(invalid()),
+        }
+
+        rest = &rest[1..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::{assemble, disassemble, parse_intcode, AssemblyError};
+
+    #[test]
+    pub fn test_assemble() {
+        let program = assemble(
+            "
+            ; Reads a number, outputs it if it's less than 8, and halts.
+                IN -> [n]
+                LT [n], #8 -> [cond]
+                jf [cond], #end
+                OUT [n]
+            end: HALT
+            n: DATA 0
+            cond: DATA 0
+            ",
+        );
+        assert_eq!(
+            Ok(parse_intcode("3,12,1007,12,8,13,1006,13,11,4,12,99,0,0")),
+            program
+        );
+    }
+
+    #[test]
+    pub fn test_assemble_operands() {
+        assert_eq!(
+            Ok(vec![21101, 3, -5, 0, 1202, 7, 4, 17, 204, -2, 109, 9, 99]),
+            assemble(
+                "
+                ADD #3, #-5, [rb]
+                MUL [rb+7], #4 -> [start+9]
+                start: OUT [rb - 2]
+                ARB #end - 3
+                end: HALT
+                "
+            )
+        );
+        assert_eq!(
+            Ok(vec![1, 2, -3, 4, 5]),
+            assemble("DATA 1, 2, -3, 4, x\nx:")
+        );
+    }
+
+    #[test]
+    pub fn test_assemble_errors() {
+        let cases = [
+            ("FOO #1", 1, "unknown mnemonic \"FOO\""),
+            (
+                "\nADD #1, #2",
+                2,
+                "ADD takes 3 operand(s), but 2 were given",
+            ),
+            (
+                "ADD #1, #2 -> #3",
+                1,
+                "ADD can't write to an immediate operand",
+            ),
+            ("OUT #1 -> [2]", 1, "OUT doesn't write to memory"),
+            (
+                "OUT 1",
+                1,
+                "operand \"1\" should be written as #n, [n] or [rb+n]",
+            ),
+            ("JT #1, #nowhere", 1, "unknown label \"nowhere\""),
+            ("a: HALT\na: HALT", 2, "label \"a\" is already defined"),
+            ("DATA 1,,2", 1, "missing operand in \"1,,2\""),
+            ("OUT #1 * 2", 1, "invalid expression \"1 * 2\""),
+        ];
+
+        for (source, line, message) in cases {
+            assert_eq!(
+                Err(AssemblyError {
+                    line,
+                    message: message.to_string()
+                }),
+                assemble(source),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_assemble_listing() {
+        for code in [
+            "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+            "1105,1,5,104,7,99,0,0,0,0,0,0,0,0,0",
+            "3,13,1007,13,8,14,1006,14,11,4,13,99,0,0,0",
+        ] {
+            let program = parse_intcode(code);
+            let listing = disassemble(&program)
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            assert_eq!(Ok(program), assemble(&listing), "{listing}");
+        }
+    }
+}
@@ -0,0 +1,605 @@
+use super::{
+    get_args_needed, interpret_opcode, writes_to_memory, IntcodeError, ModeType, ADD, HALT,
+    JMP_IF_FALSE, JMP_IF_TRUE, MNEMONICS, MULTIPLY,
+};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// The maximum number of values that a single `DATA` line holds.
+const DATA_PER_LINE: usize = 8;
+
+/// A decoded parameter of an instruction.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Parameter {
+    /// A parameter in immediate mode, written as `#5`.
+    Immediate(isize),
+    /// A parameter in position mode, written as `[100]`.
+    Position(isize),
+    /// A parameter in relative mode, written as `[rb+3]` or `[rb-3]`.
+    Relative(isize),
+}
+
+impl Parameter {
+    /// Creates a parameter from its mode and raw value.
+    ///
+    /// # Parameters
+    /// - `mode`: The mode of the parameter.
+    /// - `value`: The raw value of the parameter.
+    ///
+    /// # Returns
+    /// The parameter.
+    fn new(mode: ModeType, value: isize) -> Self {
+        match mode {
+            ModeType::Immediate => Self::Immediate(value),
+            ModeType::Position => Self::Position(value),
+            ModeType::Relative => Self::Relative(value),
+        }
+    }
+
+    /// Gets the digit used to encode this parameter's mode in an opcode.
+    ///
+    /// # Returns
+    /// The mode digit.
+    fn mode_digit(&self) -> isize {
+        match self {
+            Self::Position(_) => 0,
+            Self::Immediate(_) => 1,
+            Self::Relative(_) => 2,
+        }
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Immediate(v) => write!(f, "#{v}"),
+            Self::Position(v) => write!(f, "[{v}]"),
+            Self::Relative(v) => write!(f, "[rb{v:+}]"),
+        }
+    }
+}
+
+/// An instruction decoded from an Intcode program.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Instruction {
+    /// The address of the instruction.
+    address: usize,
+    /// The opcode, without the parameter modes.
+    opcode: isize,
+    /// The parameters of the instruction, in order.
+    parameters: Vec<Parameter>,
+}
+
+impl Instruction {
+    /// Gets the address of this instruction.
+    ///
+    /// # Returns
+    /// The address.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Gets the opcode of this instruction, without the parameter modes.
+    ///
+    /// # Returns
+    /// The opcode.
+    pub fn opcode(&self) -> isize {
+        self.opcode
+    }
+
+    /// Gets the raw opcode of this instruction, i.e., the opcode along with the parameter
+    /// modes, as it appears in the program.
+    ///
+    /// # Returns
+    /// The raw opcode.
+    pub fn raw_opcode(&self) -> isize {
+        let mut raw = self.opcode;
+        let mut place = 100;
+        for parameter in &self.parameters {
+            raw += parameter.mode_digit() * place;
+            place *= 10;
+        }
+
+        raw
+    }
+
+    /// Gets the mnemonic of this instruction (e.g., `ADD` or `JT`).
+    ///
+    /// # Returns
+    /// The mnemonic.
+    pub fn mnemonic(&self) -> &'static str {
+        MNEMONICS
+            .iter()
+            .find(|(opcode, _)| *opcode == self.opcode)
+            .map(|(_, mnemonic)| *mnemonic)
+            .unwrap()
+    }
+
+    /// Gets the parameters of this instruction.
+    ///
+    /// # Returns
+    /// The parameters, in order.
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// Gets the address right after this instruction.
+    ///
+    /// # Returns
+    /// The address of the next instruction, assuming that this instruction doesn't jump.
+    pub fn next_address(&self) -> usize {
+        self.address + 1 + self.parameters.len()
+    }
+
+    /// Whether this instruction writes to a parameter in immediate mode, which the computer
+    /// can't execute.
+    ///
+    /// # Returns
+    /// `true` if the instruction writes to an immediate parameter and `false` otherwise.
+    fn writes_immediate(&self) -> bool {
+        self.writes() && matches!(self.parameters.last(), Some(Parameter::Immediate(_)))
+    }
+
+    /// Whether the last parameter of this instruction is where it writes its result.
+    ///
+    /// # Returns
+    /// `true` if this instruction writes to memory and `false` otherwise.
+    fn writes(&self) -> bool {
+        writes_to_memory(self.opcode)
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}: {}", self.address, self.mnemonic())?;
+        let (reads, written) = match self.parameters.split_last() {
+            Some((last, rest)) if self.writes() => (rest, Some(last)),
+            _ => (&self.parameters[..], None),
+        };
+
+        for (i, parameter) in reads.iter().enumerate() {
+            write!(f, "{}{parameter}", if i == 0 { " " } else { ", " })?;
+        }
+
+        if let Some(parameter) = written {
+            write!(f, " -> {parameter}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A line in the listing of an Intcode program.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Line {
+    /// An instruction. Instructions that can't be reached from address 0 are marked as
+    /// unreachable.
+    Instruction {
+        instruction: Instruction,
+        reachable: bool,
+    },
+    /// Values that aren't part of any instruction.
+    Data { address: usize, values: Vec<isize> },
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Instruction {
+                instruction,
+                reachable,
+            } => {
+                write!(f, "{instruction}")?;
+                if !reachable {
+                    f.write_str(" ; unreachable")?;
+                }
+
+                Ok(())
+            }
+            Self::Data { address, values } => {
+                write!(f, "{address:04}: DATA ")?;
+                for (i, value) in values.iter().enumerate() {
+                    write!(f, "{}{value}", if i == 0 { "" } else { ", " })?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Decodes the instruction at the given address of the program.
+///
+/// # Parameters
+/// - `program`: The Intcode program.
+/// - `address`: The address of the instruction.
+///
+/// # Returns
+/// The instruction, or `None` if the value at the address doesn't start an instruction that
+/// the computer could execute. This is also the case if the instruction runs past the end of
+/// the program, or if its opcode has modes for parameters that it doesn't use.
+pub fn decode(program: &[isize], address: usize) -> Option<Instruction> {
+    let raw_opcode = *program.get(address)?;
+    let instruction = decode_lenient(|a| *program.get(a).unwrap_or(&0), address).ok()?;
+    if instruction.next_address() > program.len()
+        || instruction.writes_immediate()
+        || instruction.raw_opcode() != raw_opcode
+    {
+        return None;
+    }
+
+    Some(instruction)
+}
+
+/// Decodes the instruction at the given address of the program the way that the computer
+/// would, reading any parameters past the end of the program as `0`.
+///
+/// # Parameters
+/// - `read`: Reads the value at an address of the Intcode program, which should be `0` past
+///   the end of the program.
+/// - `address`: The address of the instruction.
+///
+/// # Returns
+/// The instruction, or an error if the opcode or a parameter mode is invalid. An address past
+/// the end of the program holds an unknown opcode of `0`.
+pub(super) fn decode_lenient(
+    read: impl Fn(usize) -> isize,
+    address: usize,
+) -> Result<Instruction, IntcodeError> {
+    let (opcode, p1, p2, p3) = interpret_opcode(address, read(address))?;
+    // `interpret_opcode` already made sure that the opcode is valid.
+    let num_args = get_args_needed(opcode).unwrap();
+    Ok(Instruction {
+        address,
+        opcode,
+        parameters: [p1, p2, p3]
+            .into_iter()
+            .take(num_args)
+            .enumerate()
+            .map(|(i, mode)| Parameter::new(mode, read(address + 1 + i)))
+            .collect(),
+    })
+}
+
+/// Finds every instruction that can be reached by following the control flow of the program,
+/// starting at address 0.
+///
+/// Jumps whose target is read from memory can't be followed directly. Intcode programs
+/// usually implement calls by storing a constant return address (e.g., `ADD #25, #0 ->
+/// [rb+1]`) and later jumping back to it, so if the program has any such jumps, constants
+/// stored this way that point past the instruction storing them are also treated as code.
+/// Self-modifying code isn't accounted for.
+///
+/// # Parameters
+/// - `program`: The Intcode program.
+///
+/// # Returns
+/// The reachable instructions, keyed by address.
+fn find_reachable(program: &[isize]) -> BTreeMap<usize, Instruction> {
+    let mut reachable: BTreeMap<usize, Instruction> = BTreeMap::new();
+    let mut stack = vec![0];
+    let mut stored_constants = vec![];
+    let mut has_indirect_jump = false;
+    loop {
+        while let Some(address) = stack.pop() {
+            if reachable.contains_key(&address) {
+                continue;
+            }
+
+            let Some(instruction) = decode(program, address) else {
+                continue;
+            };
+
+            let next = instruction.next_address();
+            match (instruction.opcode, instruction.parameters.as_slice()) {
+                (HALT, _) => {}
+                (JMP_IF_TRUE | JMP_IF_FALSE, [condition, target]) => {
+                    let (may_jump, may_continue) = match condition {
+                        Parameter::Immediate(v) => {
+                            let jumps = (*v != 0) == (instruction.opcode == JMP_IF_TRUE);
+                            (jumps, !jumps)
+                        }
+                        _ => (true, true),
+                    };
+
+                    if may_continue {
+                        stack.push(next);
+                    }
+
+                    if may_jump {
+                        match target {
+                            Parameter::Immediate(t) => stack.extend(usize::try_from(*t).ok()),
+                            _ => has_indirect_jump = true,
+                        }
+                    }
+                }
+                (ADD | MULTIPLY, [Parameter::Immediate(a), Parameter::Immediate(b), _]) => {
+                    let value = if instruction.opcode == ADD {
+                        a.checked_add(*b)
+                    } else {
+                        a.checked_mul(*b)
+                    };
+
+                    stored_constants.extend(
+                        value
+                            .and_then(|v| usize::try_from(v).ok())
+                            .filter(|v| *v > address),
+                    );
+                    stack.push(next);
+                }
+                _ => stack.push(next),
+            }
+
+            reachable.insert(address, instruction);
+        }
+
+        if !has_indirect_jump {
+            break;
+        }
+
+        // Don't treat constants that point into the middle of known code as code.
+        stack.extend(stored_constants.drain(..).filter(|address| {
+            reachable
+                .range(..=*address)
+                .next_back()
+                .is_none_or(|(_, instruction)| instruction.next_address() <= *address)
+        }));
+
+        if stack.is_empty() {
+            break;
+        }
+    }
+
+    reachable
+}
+
+/// Disassembles an Intcode program into a listing. Each line either holds an instruction,
+/// like `0010: ADD [rb+3], #5 -> [100]`, or values that aren't part of any instruction, like
+/// `0014: DATA 0, 0, 7`.
+///
+/// Instructions are found by following the control flow from address 0 (see
+/// [`decode`] for what counts as an instruction). Values that don't belong to a reachable
+/// instruction are still shown as instructions if they decode to one, but these are marked as
+/// unreachable since they may just be data that happens to look like code.
+///
+/// # Parameters
+/// - `program`: The Intcode program.
+///
+/// # Returns
+/// The lines of the listing, in order of address. Every value in the program is part of
+/// exactly one line.
+pub fn disassemble(program: &[isize]) -> Vec<Line> {
+    let mut reachable = find_reachable(program);
+    let mut lines = vec![];
+    let mut data_start = 0;
+    let mut data = vec![];
+    let mut address = 0;
+    while address < program.len() {
+        let decoded = match reachable.remove(&address) {
+            Some(instruction) => Some((instruction, true)),
+            // An unreachable instruction shouldn't hide the start of a reachable one.
+            None => decode(program, address)
+                .filter(|i| reachable.range(address..i.next_address()).next().is_none())
+                .map(|instruction| (instruction, false)),
+        };
+
+        match decoded {
+            Some((instruction, reachable)) => {
+                flush_data(&mut lines, data_start, &mut data);
+                address = instruction.next_address();
+                lines.push(Line::Instruction {
+                    instruction,
+                    reachable,
+                });
+            }
+            None => {
+                if data.is_empty() {
+                    data_start = address;
+                }
+
+                data.push(program[address]);
+                address += 1;
+                if data.len() == DATA_PER_LINE {
+                    flush_data(&mut lines, data_start, &mut data);
+                }
+            }
+        }
+    }
+
+    flush_data(&mut lines, data_start, &mut data);
+    lines
+}
+
+/// Disassembles part of a program's memory into a listing, e.g., memory far past the end of
+/// the program that can't be disassembled along with the rest of it. The control flow isn't
+/// followed, so every value that decodes to an instruction is shown as one, and every line is
+/// marked as reachable.
+///
+/// # Parameters
+/// - `values`: The values in this part of the memory.
+/// - `start`: The address of the first value.
+///
+/// # Returns
+/// The lines of the listing, in order of address. Every value is part of exactly one line.
+pub fn disassemble_window(values: &[isize], start: usize) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut data_start = 0;
+    let mut data = vec![];
+    let mut offset = 0;
+    while offset < values.len() {
+        match decode(values, offset) {
+            Some(mut instruction) => {
+                flush_data(&mut lines, start + data_start, &mut data);
+                offset = instruction.next_address();
+                instruction.address += start;
+                lines.push(Line::Instruction {
+                    instruction,
+                    reachable: true,
+                });
+            }
+            None => {
+                if data.is_empty() {
+                    data_start = offset;
+                }
+
+                data.push(values[offset]);
+                offset += 1;
+                if data.len() == DATA_PER_LINE {
+                    flush_data(&mut lines, start + data_start, &mut data);
+                }
+            }
+        }
+    }
+
+    flush_data(&mut lines, start + data_start, &mut data);
+    lines
+}
+
+/// Adds a `DATA` line with the pending values to the listing, if there are any.
+///
+/// # Parameters
+/// - `lines`: The lines of the listing.
+/// - `address`: The address of the first pending value.
+/// - `data`: The pending values. This will be emptied.
+fn flush_data(lines: &mut Vec<Line>, address: usize, data: &mut Vec<isize>) {
+    if !data.is_empty() {
+        lines.push(Line::Data {
+            address,
+            values: std::mem::take(data),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::{decode, disassemble, disassemble_window, parse_intcode, Line, Parameter};
+
+    fn listing(program: &str) -> Vec<String> {
+        disassemble(&parse_intcode(program))
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    #[test]
+    pub fn test_decode() {
+        let program = parse_intcode("21101,3,5,100,99");
+        let instruction = decode(&program, 0).unwrap();
+        assert_eq!("ADD", instruction.mnemonic());
+        assert_eq!(21101, instruction.raw_opcode());
+        assert_eq!(4, instruction.next_address());
+        assert_eq!(
+            &[
+                Parameter::Immediate(3),
+                Parameter::Immediate(5),
+                Parameter::Relative(100)
+            ],
+            instruction.parameters()
+        );
+        assert_eq!("0000: ADD #3, #5 -> [rb+100]", instruction.to_string());
+
+        // Unknown opcode, writing in immediate mode, unused mode and truncated instruction.
+        for program in ["42", "11101,1,1,1", "31105,1,0", "1,0,0"] {
+            assert_eq!(None, decode(&parse_intcode(program), 0), "{program}");
+        }
+    }
+
+    #[test]
+    pub fn test_disassemble() {
+        // Reads a number, outputs it if it's less than 8, and halts.
+        assert_eq!(
+            vec![
+                "0000: IN -> [13]",
+                "0002: LT [13], #8 -> [14]",
+                "0006: JF [14], #11",
+                "0009: OUT [13]",
+                "0011: HALT",
+                "0012: DATA 0, 0, 0",
+            ],
+            listing("3,13,1007,13,8,14,1006,14,11,4,13,99,0,0,0")
+        );
+
+        assert_eq!(
+            vec![
+                "0000: ARB #-4",
+                "0002: OUT [rb-1]",
+                "0004: HALT",
+                "0005: DATA 7",
+            ],
+            listing("109,-4,204,-1,99,7")
+        );
+    }
+
+    #[test]
+    pub fn test_disassemble_unreachable() {
+        // The jump always happens, so the output at address 3 is never run.
+        assert_eq!(
+            vec![
+                "0000: JT #1, #5",
+                "0003: OUT #7 ; unreachable",
+                "0005: HALT",
+                "0006: DATA 0, 0, 0, 0, 0, 0, 0, 0",
+                "0014: DATA 0",
+            ],
+            listing("1105,1,5,104,7,99,0,0,0,0,0,0,0,0,0")
+        );
+    }
+
+    #[test]
+    pub fn test_disassemble_call_and_return() {
+        // Stores the return address 9 in [rb+0], calls the function at address 10, which
+        // outputs 1 and returns. Address 9 is only reachable through the return.
+        let lines = disassemble(&parse_intcode(
+            "109,20,21101,9,0,0,1105,1,10,99,104,1,2105,1,0",
+        ));
+        assert_eq!(
+            vec![
+                "0000: ARB #20",
+                "0002: ADD #9, #0 -> [rb+0]",
+                "0006: JT #1, #10",
+                "0009: HALT",
+                "0010: OUT #1",
+                "0012: JT #1, [rb+0]",
+            ],
+            lines.iter().map(|l| l.to_string()).collect::<Vec<_>>()
+        );
+        assert!(lines
+            .iter()
+            .all(|l| matches!(l, Line::Instruction { reachable, .. } if *reachable)));
+    }
+
+    #[test]
+    pub fn test_disassemble_covers_program() {
+        let program = parse_intcode("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        let mut next = 0;
+        for line in disassemble(&program) {
+            let (address, len) = match &line {
+                Line::Instruction { instruction, .. } => (
+                    instruction.address(),
+                    instruction.next_address() - instruction.address(),
+                ),
+                Line::Data { address, values } => (*address, values.len()),
+            };
+
+            assert_eq!(next, address);
+            next += len;
+        }
+
+        assert_eq!(program.len(), next);
+    }
+
+    #[test]
+    pub fn test_disassemble_window() {
+        let lines = disassemble_window(&[0, 1101, 1, 2, 3, 99, 0, 0], 1 << 40);
+        assert_eq!(
+            vec![
+                "1099511627776: DATA 0",
+                "1099511627777: ADD #1, #2 -> [3]",
+                "1099511627781: HALT",
+                "1099511627782: DATA 0, 0",
+            ],
+            lines.iter().map(|l| l.to_string()).collect::<Vec<_>>()
+        );
+        assert!(disassemble_window(&[], 7).is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+use super::{interpret_opcode, ModeType, Word};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The number of low bits of an address that index into a page.
+const PAGE_BITS: usize = 10;
+/// The number of values in a page.
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+/// The number of pages past the end of the dense pages that a write can be for the dense pages
+/// to grow up to it. Writes any further away get their own page in the sparse pages.
+const MAX_DENSE_GAP: usize = 64;
+
+/// The opcode and parameter modes of a value, decoded ahead of time so that executing an
+/// instruction doesn't have to decode it again.
+#[derive(Copy, Clone)]
+pub(super) struct Decoded {
+    /// The opcode, without the parameter modes.
+    pub(super) opcode: u8,
+    /// The modes of the three parameters.
+    pub(super) modes: [ModeType; 3],
+}
+
+impl Decoded {
+    /// Decodes a value as an instruction.
+    ///
+    /// # Parameters
+    /// - `value`: The value.
+    ///
+    /// # Returns
+    /// The decoded instruction, or `None` if the value isn't a valid instruction.
+    fn new<W: Word>(value: W) -> Option<Self> {
+        interpret_opcode(0, value)
+            .ok()
+            .map(|(opcode, p1, p2, p3)| Self {
+                opcode: opcode as u8,
+                modes: [p1, p2, p3],
+            })
+    }
+}
+
+/// A fixed-size chunk of memory.
+#[derive(Clone)]
+struct Page<W> {
+    /// The values in the page.
+    values: [W; PAGE_SIZE],
+    /// Each value decoded as an instruction. An entry may be `None` even if the value is a
+    /// valid instruction (e.g., after it was changed through [`Memory::get_mut`]), in which
+    /// case it has to be decoded on the spot.
+    decoded: [Option<Decoded>; PAGE_SIZE],
+}
+
+impl<W: Word> Page<W> {
+    /// Creates a page filled with zeros.
+    ///
+    /// # Returns
+    /// The page.
+    fn new() -> Self {
+        Self {
+            values: [W::ZERO; PAGE_SIZE],
+            decoded: [None; PAGE_SIZE],
+        }
+    }
+}
+
+/// The memory of an Intcode computer, split into pages that are shared between copies until
+/// one of them writes to it (copy-on-write). This makes cloning memory, e.g., to reset the
+/// computer or take a snapshot, only cost one pointer per page.
+///
+/// Pages from the start of memory are kept in order, so that they can be found quickly. A
+/// page far past those (e.g., for a single write to address 2^40) is kept in a map instead,
+/// so that the memory in between doesn't need to be allocated. Pages that were never written
+/// to all share a single page of zeros.
+#[derive(Clone)]
+pub(super) struct Memory<W> {
+    /// The pages from the start of memory, in order of address.
+    dense: Vec<Arc<Page<W>>>,
+    /// The pages past the dense pages that have been written to, by page index.
+    sparse: BTreeMap<usize, Arc<Page<W>>>,
+    /// The page of zeros used for pages that have never been written to.
+    zero: Arc<Page<W>>,
+    /// One more than the highest address that's part of the program or has been written to.
+    len: usize,
+}
+
+impl<W: Word> Memory<W> {
+    /// Creates memory holding the given program.
+    ///
+    /// # Parameters
+    /// - `program`: The program.
+    ///
+    /// # Returns
+    /// The memory.
+    pub(super) fn new(program: &[W]) -> Self {
+        let dense = program
+            .chunks(PAGE_SIZE)
+            .map(|chunk| {
+                let mut page = Page::new();
+                for (i, value) in chunk.iter().enumerate() {
+                    page.values[i] = *value;
+                    page.decoded[i] = Decoded::new(*value);
+                }
+
+                Arc::new(page)
+            })
+            .collect();
+
+        Self {
+            dense,
+            sparse: BTreeMap::new(),
+            zero: Arc::new(Page::new()),
+            len: program.len(),
+        }
+    }
+
+    /// Creates memory of the given length, filled with zeros.
+    ///
+    /// # Parameters
+    /// - `len`: The length.
+    ///
+    /// # Returns
+    /// The memory.
+    pub(super) fn zeroed(len: usize) -> Self {
+        Self {
+            len,
+            ..Self::new(&[])
+        }
+    }
+
+    /// Gets the length of the memory.
+    ///
+    /// # Returns
+    /// One more than the highest address that's part of the program or has been written to.
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Gets the value at the given address.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    ///
+    /// # Returns
+    /// The value, or `0` if the address is past the end of the memory.
+    pub(super) fn get(&self, address: usize) -> W {
+        self.get_ref(address).copied().unwrap_or(W::ZERO)
+    }
+
+    /// Gets a reference to the value at the given address.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    ///
+    /// # Returns
+    /// The value, or `None` if the address is past the end of the memory.
+    pub(super) fn get_ref(&self, address: usize) -> Option<&W> {
+        if address >= self.len {
+            return None;
+        }
+
+        Some(&self.page(address >> PAGE_BITS).values[address & (PAGE_SIZE - 1)])
+    }
+
+    /// Gets the value at the given address, decoded as an instruction.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    ///
+    /// # Returns
+    /// The decoded instruction, or `None` if it wasn't decoded ahead of time.
+    pub(super) fn decoded(&self, address: usize) -> Option<Decoded> {
+        if address >= self.len {
+            return None;
+        }
+
+        self.page(address >> PAGE_BITS).decoded[address & (PAGE_SIZE - 1)]
+    }
+
+    /// Sets the value at the given address, growing the memory if needed.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    /// - `value`: The new value.
+    pub(super) fn set(&mut self, address: usize, value: W) {
+        let (page, offset) = self.page_mut(address);
+        page.values[offset] = value;
+        page.decoded[offset] = Decoded::new(value);
+    }
+
+    /// Gets a mutable reference to the value at the given address, growing the memory if
+    /// needed. Prefer [`Memory::set`], since the value has to be decoded again every time
+    /// it's executed.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    ///
+    /// # Returns
+    /// The value.
+    pub(super) fn get_mut(&mut self, address: usize) -> &mut W {
+        let (page, offset) = self.page_mut(address);
+        page.decoded[offset] = None;
+        &mut page.values[offset]
+    }
+
+    /// Copies the memory into a vector. Note that this copies every value up to the length of
+    /// the memory, which can be a lot if the program wrote to a high address.
+    ///
+    /// # Returns
+    /// The values in the memory, in order of address.
+    pub(super) fn to_vec(&self) -> Vec<W> {
+        (0..self.len.div_ceil(PAGE_SIZE))
+            .flat_map(|idx| self.page(idx).values.iter().copied())
+            .take(self.len)
+            .collect()
+    }
+
+    /// Gets the parts of the memory that aren't all zeros, a page at a time.
+    ///
+    /// # Returns
+    /// An iterator over the address of the first value of each part and its values. Leading
+    /// and trailing zeros are left out.
+    pub(super) fn segments(&self) -> impl Iterator<Item = (usize, &[W])> {
+        self.dense
+            .iter()
+            .enumerate()
+            .chain(self.sparse.iter().map(|(idx, page)| (*idx, page)))
+            .filter(|(_, page)| !Arc::ptr_eq(page, &self.zero))
+            .filter_map(|(idx, page)| {
+                let page_start = idx << PAGE_BITS;
+                let values = &page.values[..(self.len - page_start).min(PAGE_SIZE)];
+                let start = values.iter().position(|v| *v != W::ZERO)?;
+                let end = values.iter().rposition(|v| *v != W::ZERO)? + 1;
+                Some((page_start + start, &values[start..end]))
+            })
+    }
+
+    /// Gets the page with the given index.
+    ///
+    /// # Parameters
+    /// - `idx`: The index of the page.
+    ///
+    /// # Returns
+    /// The page, which is the page of zeros if it was never written to.
+    fn page(&self, idx: usize) -> &Page<W> {
+        match self.dense.get(idx) {
+            Some(page) => page,
+            None => self.sparse.get(&idx).unwrap_or(&self.zero),
+        }
+    }
+
+    /// Gets the page containing the given address so that it can be changed, growing the
+    /// memory and copying the page (if it's shared) as needed.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    ///
+    /// # Returns
+    /// A tuple where the first element is the page and the second element is the offset of
+    /// the address in the page.
+    fn page_mut(&mut self, address: usize) -> (&mut Page<W>, usize) {
+        let page_idx = address >> PAGE_BITS;
+        self.len = self.len.max(address + 1);
+        let page = if page_idx < self.dense.len() + MAX_DENSE_GAP {
+            while self.dense.len() <= page_idx {
+                let page = self
+                    .sparse
+                    .remove(&self.dense.len())
+                    .unwrap_or_else(|| self.zero.clone());
+                self.dense.push(page);
+            }
+
+            &mut self.dense[page_idx]
+        } else {
+            self.sparse
+                .entry(page_idx)
+                .or_insert_with(|| self.zero.clone())
+        };
+
+        // The page of zeros is always shared, so it's copied before it's changed.
+        (Arc::make_mut(page), address & (PAGE_SIZE - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::memory::{Memory, PAGE_SIZE};
+
+    #[test]
+    pub fn test_sparse_write() {
+        let mut memory = Memory::new(&[1, 2, 3]);
+        memory.set(1 << 40, 5);
+        assert_eq!((1 << 40) + 1, memory.len());
+        assert_eq!(1, memory.dense.len());
+        assert_eq!(1, memory.sparse.len());
+        assert_eq!(5, memory.get(1 << 40));
+        assert_eq!(0, memory.get((1 << 40) - 1));
+        assert_eq!(Some(&0), memory.get_ref(1 << 39));
+        assert_eq!(
+            vec![(0, [1, 2, 3].as_slice()), (1 << 40, [5].as_slice())],
+            memory.segments().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_dense_growth() {
+        let mut memory = Memory::new(&[1]);
+        memory.set(PAGE_SIZE * 100, 7);
+        assert_eq!(1, memory.dense.len());
+
+        // Growing the dense pages up to a sparse page moves it over.
+        memory.set(PAGE_SIZE * 60, 6);
+        memory.set(PAGE_SIZE * 110, 8);
+        assert_eq!(111, memory.dense.len());
+        assert!(memory.sparse.is_empty());
+        assert_eq!(7, memory.get(PAGE_SIZE * 100));
+        assert_eq!(8, memory.get(PAGE_SIZE * 110));
+        assert_eq!(PAGE_SIZE * 110 + 1, memory.to_vec().len());
+    }
+
+    #[test]
+    pub fn test_copy_on_write() {
+        let mut memory = Memory::new(&[1, 2]);
+        let copy = memory.clone();
+        memory.set(0, 9);
+        memory.set(PAGE_SIZE * 2, 3);
+        assert_eq!(1, copy.get(0));
+        assert_eq!(2, copy.len());
+        assert_eq!(0, copy.get(PAGE_SIZE * 2));
+
+        // Pages that were never written to stay shared with the page of zeros.
+        assert_eq!(0, memory.zero.values.iter().filter(|v| **v != 0).count());
+        assert_eq!(0, memory.get(PAGE_SIZE));
+    }
+}
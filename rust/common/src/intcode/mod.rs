@@ -0,0 +1,1701 @@
+use memory::Memory;
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+mod ascii;
+mod asm;
+mod disasm;
+mod memory;
+mod network;
+mod snapshot;
+mod word;
+pub use ascii::AsciiOutput;
+pub use asm::{assemble, AssemblyError};
+pub use disasm::{decode, disassemble, disassemble_window, Instruction, Line, Parameter};
+pub use network::{Network, NetworkState, Packet, Routing};
+pub use snapshot::Snapshot;
+pub use word::Word;
+
+const ADD: isize = 1;
+const MULTIPLY: isize = 2;
+const INPUT: isize = 3;
+const OUTPUT: isize = 4;
+const JMP_IF_TRUE: isize = 5;
+const JMP_IF_FALSE: isize = 6;
+const LESS_THAN: isize = 7;
+const EQUALS: isize = 8;
+const RELATIVE: isize = 9;
+const HALT: isize = 99;
+
+/// The most values that copying the whole memory (e.g., with
+/// [`IntCodeComputer::view_program`]) can copy. A program can write to very high addresses, so
+/// this avoids trying to allocate far more than any real program uses.
+const MAX_COPY_LEN: usize = 1 << 24;
+
+/// The mnemonic for each opcode, as used in listings.
+const MNEMONICS: [(isize, &str); 10] = [
+    (ADD, "ADD"),
+    (MULTIPLY, "MUL"),
+    (INPUT, "IN"),
+    (OUTPUT, "OUT"),
+    (JMP_IF_TRUE, "JT"),
+    (JMP_IF_FALSE, "JF"),
+    (LESS_THAN, "LT"),
+    (EQUALS, "EQ"),
+    (RELATIVE, "ARB"),
+    (HALT, "HALT"),
+];
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+enum ModeType {
+    /// Causes a parameter to be interpreted as a value. For example, if the parameter is 50, its
+    /// value is simply 50.
+    Immediate,
+    /// Causes the parameter to be interpreted as a position. For example, if the parameter is 50,
+    /// its value is the value stored at address 50 in memory.
+    Position,
+    /// Causes the parameter to be interpreted relative to some relative base.  
+    Relative,
+}
+
+impl TryFrom<isize> for ModeType {
+    type Error = isize;
+
+    fn try_from(val: isize) -> Result<Self, Self::Error> {
+        match val {
+            0 => Ok(Self::Position),
+            1 => Ok(Self::Immediate),
+            2 => Ok(Self::Relative),
+            _ => Err(val),
+        }
+    }
+}
+
+/// The reason that an [`IntCodeComputer`] stopped running.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum RunState<W = isize> {
+    /// The computer reached a HALT instruction, or the instruction pointer went past the end of
+    /// the program.
+    Halted,
+    /// The computer reached an input instruction, but there was no input available.
+    NeedsInput,
+    /// The computer produced the given output.
+    Output(W),
+    /// The computer reached a breakpoint and stopped right before executing the instruction at
+    /// the given address.
+    Breakpoint(usize),
+    /// The last instruction wrote `new` to a watched address that used to hold `old`.
+    Watchpoint { address: usize, old: W, new: W },
+}
+
+/// An error caused by a program that the computer can't execute. Each error records the
+/// instruction pointer and the raw opcode (i.e., the value at the instruction pointer) of the
+/// instruction that caused it.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum IntcodeError<W = isize> {
+    /// The instruction's opcode isn't one that the computer knows about.
+    UnknownOpcode { ins_pointer: usize, raw_opcode: W },
+    /// One of the instruction's parameters has a mode other than 0, 1 or 2.
+    InvalidParameterMode {
+        ins_pointer: usize,
+        raw_opcode: W,
+        mode: isize,
+    },
+    /// The instruction tried to write to a parameter in immediate mode.
+    ImmediateModeWrite { ins_pointer: usize, raw_opcode: W },
+    /// The instruction tried to read from, write to, or jump to a negative address.
+    NegativeAddress {
+        ins_pointer: usize,
+        raw_opcode: W,
+        address: W,
+    },
+    /// The instruction tried to read from, write to, or jump to an address larger than
+    /// `isize::MAX`.
+    AddressTooLarge {
+        ins_pointer: usize,
+        raw_opcode: W,
+        address: W,
+    },
+    /// The result of the instruction's arithmetic, or of adding to the relative base, doesn't
+    /// fit in the computer's word type.
+    Overflow { ins_pointer: usize, raw_opcode: W },
+}
+
+impl<W: Word> IntcodeError<W> {
+    /// Gets the instruction pointer of the instruction that caused this error.
+    ///
+    /// # Returns
+    /// The instruction pointer.
+    pub fn ins_pointer(&self) -> usize {
+        match *self {
+            Self::UnknownOpcode { ins_pointer, .. }
+            | Self::InvalidParameterMode { ins_pointer, .. }
+            | Self::ImmediateModeWrite { ins_pointer, .. }
+            | Self::NegativeAddress { ins_pointer, .. }
+            | Self::AddressTooLarge { ins_pointer, .. }
+            | Self::Overflow { ins_pointer, .. } => ins_pointer,
+        }
+    }
+
+    /// Gets the raw opcode of the instruction that caused this error.
+    ///
+    /// # Returns
+    /// The raw opcode, including the parameter modes.
+    pub fn raw_opcode(&self) -> W {
+        match *self {
+            Self::UnknownOpcode { raw_opcode, .. }
+            | Self::InvalidParameterMode { raw_opcode, .. }
+            | Self::ImmediateModeWrite { raw_opcode, .. }
+            | Self::NegativeAddress { raw_opcode, .. }
+            | Self::AddressTooLarge { raw_opcode, .. }
+            | Self::Overflow { raw_opcode, .. } => raw_opcode,
+        }
+    }
+}
+
+impl<W: Word> Display for IntcodeError<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "instruction {} at address {}: ",
+            self.raw_opcode(),
+            self.ins_pointer()
+        )?;
+        match self {
+            Self::UnknownOpcode { .. } => f.write_str("unknown opcode"),
+            Self::InvalidParameterMode { mode, .. } => write!(f, "invalid parameter mode {mode}"),
+            Self::ImmediateModeWrite { .. } => f.write_str("cannot write in immediate mode"),
+            Self::NegativeAddress { address, .. } => write!(f, "negative address {address}"),
+            Self::AddressTooLarge { address, .. } => write!(f, "address {address} is too large"),
+            Self::Overflow { .. } => f.write_str("arithmetic overflow"),
+        }
+    }
+}
+
+impl<W: Word> Error for IntcodeError<W> {}
+
+/// A function that's called right before each instruction is executed. If it returns an error,
+/// the computer stops with that error.
+type Tracer<'a, W> = &'a mut dyn FnMut(&IntCodeComputer<W>) -> Result<(), IntcodeError<W>>;
+
+/// An Intcode computer. Cloning a computer is cheap: the clone shares the computer's memory
+/// until one of them writes to it.
+///
+/// Values in memory are `isize` by default, but any [`Word`] type (e.g., `i128`) can be used
+/// instead. Arithmetic that doesn't fit in the word type is reported as an
+/// [`IntcodeError::Overflow`] rather than wrapping around. Memory is only allocated for the
+/// parts of it that the program uses, so a program can write to any address up to
+/// `isize::MAX`.
+#[derive(Clone)]
+pub struct IntCodeComputer<W = isize> {
+    /// The original program code.
+    original: Memory<W>,
+    /// The current program code.
+    memory: Memory<W>,
+    /// The instruction pointer.
+    ins_pointer: usize,
+    /// The output from opcode 4.
+    stdout: Vec<W>,
+    /// The input for opcode 3.
+    stdin: VecDeque<W>,
+    /// Whether the computer is halted.
+    halted: bool,
+    /// The relative base.
+    relative_base: W,
+    /// The addresses of instructions to stop before.
+    breakpoints: HashSet<usize>,
+    /// The opcodes of instructions to stop before.
+    opcode_breakpoints: HashSet<isize>,
+    /// The addresses to stop after writing to.
+    watchpoints: HashSet<usize>,
+    /// The address of the instruction that the computer last stopped before, so that running
+    /// again doesn't stop there immediately.
+    paused_at: Option<usize>,
+    /// The address, old value and new value of the last write to a watched address.
+    watchpoint_hit: Option<(usize, W, W)>,
+    /// Whether instructions that were decoded ahead of time are used.
+    predecoding: bool,
+}
+
+impl<W: Word> IntCodeComputer<W> {
+    /// Creates a new IntCodeComputer with the specified program.
+    ///
+    /// # Parameters
+    /// - `prgm`: The program.
+    /// - `input`: The input, to be processed in a queue.
+    ///
+    /// # Returns
+    /// THe new computer.
+    pub fn new(prgm: &[W], input: Option<Vec<W>>) -> Self {
+        let memory = Memory::new(prgm);
+        IntCodeComputer {
+            original: memory.clone(),
+            memory,
+            ins_pointer: 0,
+            stdout: vec![],
+            stdin: match input {
+                Some(v) => VecDeque::from(v),
+                None => VecDeque::new(),
+            },
+            halted: false,
+            relative_base: W::ZERO,
+            breakpoints: HashSet::new(),
+            opcode_breakpoints: HashSet::new(),
+            watchpoints: HashSet::new(),
+            paused_at: None,
+            watchpoint_hit: None,
+            predecoding: true,
+        }
+    }
+
+    /// Inputs the value to the computer's input queue.
+    ///
+    /// # Parameters
+    /// - `input`: The input.
+    pub fn input_to_stdin(&mut self, input: W) {
+        self.stdin.push_back(input);
+    }
+
+    /// Resets the program to the default. This doesn't copy the program; its memory is only
+    /// copied when the program writes to it.
+    pub fn reset(&mut self) {
+        self.ins_pointer = 0;
+        self.memory = self.original.clone();
+        self.stdin.clear();
+        self.stdout.clear();
+        self.halted = false;
+        self.relative_base = W::ZERO;
+        self.paused_at = None;
+        self.watchpoint_hit = None;
+    }
+
+    /// Gets a copy of the current program.
+    ///
+    /// # Returns
+    /// The program, including any memory past the end of the program that it wrote to. Note
+    /// that if the program wrote to a high address, this is at least that long.
+    ///
+    /// # Panics
+    /// If the memory is too long to copy, e.g., because the program wrote to a very high
+    /// address. Use [`IntCodeComputer::view_memory`] to copy part of the memory instead.
+    #[allow(dead_code)]
+    pub fn view_program(&self) -> Vec<W> {
+        self.copy_memory()
+    }
+
+    /// Gets a copy of part of the memory.
+    ///
+    /// # Parameters
+    /// - `start`: The address of the first value.
+    /// - `len`: The number of values.
+    ///
+    /// # Returns
+    /// The values at addresses `start` to `start + len - 1`, where any address past the end of
+    /// the memory holds `0`.
+    pub fn view_memory(&self, start: usize, len: usize) -> Vec<W> {
+        (start..start.saturating_add(len))
+            .map(|address| self.memory.get(address))
+            .collect()
+    }
+
+    /// Copies the whole memory into a vector.
+    ///
+    /// # Returns
+    /// The values in the memory, in order of address.
+    ///
+    /// # Panics
+    /// If the memory is longer than [`MAX_COPY_LEN`].
+    fn copy_memory(&self) -> Vec<W> {
+        assert!(
+            self.memory.len() <= MAX_COPY_LEN,
+            "memory of length {} is too long to copy",
+            self.memory.len()
+        );
+        self.memory.to_vec()
+    }
+
+    /// Gets the length of the computer's memory.
+    ///
+    /// # Returns
+    /// One more than the highest address that's part of the program or has been written to.
+    pub fn memory_len(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Gets an immutable view into the standard output.
+    ///
+    /// # Returns
+    /// An immutable view into the standard output of the program.
+    #[allow(dead_code)]
+    pub fn view_stdout(&self) -> &[W] {
+        &self.stdout
+    }
+
+    /// Runs the program until it halts, produces an output, or needs input that isn't available
+    /// yet, whichever occurs first. The computer also stops at breakpoints and watchpoints.
+    ///
+    /// If the program needs input, the instruction pointer stays on the input instruction, so
+    /// calling this again after [`IntCodeComputer::input_to_stdin`] resumes the program where it
+    /// left off. Likewise, running again after a breakpoint executes the instruction that the
+    /// computer stopped at.
+    ///
+    /// # Returns
+    /// Why the computer stopped running. Any output is also added to the standard output.
+    ///
+    /// # Panics
+    /// If the program can't be executed. Use [`IntCodeComputer::try_run`] to handle this case.
+    pub fn run(&mut self) -> RunState<W> {
+        self.try_run().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Runs the program until it halts, produces an output, or needs input that isn't available
+    /// yet, whichever occurs first. This is like [`IntCodeComputer::run`], but returns an
+    /// error instead of panicking if the program can't be executed.
+    ///
+    /// If an error occurs, the instruction pointer stays on the offending instruction.
+    ///
+    /// # Returns
+    /// Why the computer stopped running, or the error that stopped the program.
+    pub fn try_run(&mut self) -> Result<RunState<W>, IntcodeError<W>> {
+        self.run_traced(None)
+    }
+
+    /// Runs the program, optionally tracing each instruction.
+    ///
+    /// # Parameters
+    /// - `trace`: The function to call before each instruction is executed, if any.
+    ///
+    /// # Returns
+    /// Why the computer stopped running, or the error that stopped the program.
+    fn run_traced(&mut self, mut trace: Option<Tracer<W>>) -> Result<RunState<W>, IntcodeError<W>> {
+        // If the computer stopped at this instruction last time, don't stop here again.
+        let mut resuming = self.paused_at.take() == Some(self.ins_pointer);
+        loop {
+            if !resuming && self.at_breakpoint() {
+                self.paused_at = Some(self.ins_pointer);
+                return Ok(RunState::Breakpoint(self.ins_pointer));
+            }
+
+            resuming = false;
+            if let Some(trace) = trace.as_mut() {
+                if self.ins_pointer < self.memory.len() {
+                    trace(self)?;
+                }
+            }
+
+            match self.try_step()? {
+                None => {}
+                Some(RunState::NeedsInput) => {
+                    // The breakpoint at this instruction, if any, was already reported.
+                    self.paused_at = Some(self.ins_pointer);
+                    return Ok(RunState::NeedsInput);
+                }
+                Some(state) => return Ok(state),
+            }
+        }
+    }
+
+    /// Executes the instruction at the instruction pointer, ignoring any breakpoints.
+    ///
+    /// # Returns
+    /// `None` if the instruction was executed and the program can keep running, or the reason
+    /// that the computer had to stop otherwise.
+    ///
+    /// # Panics
+    /// If the instruction can't be executed. Use [`IntCodeComputer::try_step`] to handle this
+    /// case.
+    pub fn step(&mut self) -> Option<RunState<W>> {
+        self.try_step().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Executes the instruction at the instruction pointer, ignoring any breakpoints. This is
+    /// like [`IntCodeComputer::step`], but returns an error instead of panicking if the
+    /// instruction can't be executed.
+    ///
+    /// # Returns
+    /// `None` if the instruction was executed and the program can keep running, the reason
+    /// that the computer had to stop otherwise, or the error that stopped the program.
+    pub fn try_step(&mut self) -> Result<Option<RunState<W>>, IntcodeError<W>> {
+        let ins_pointer = self.ins_pointer;
+        let state = self.execute();
+        // Once the computer moves on, the breakpoint it last stopped at can be hit again.
+        if self.ins_pointer != ins_pointer {
+            self.paused_at = None;
+        }
+
+        state
+    }
+
+    /// Executes the instruction at the instruction pointer.
+    ///
+    /// # Returns
+    /// `None` if the instruction was executed and the program can keep running, the reason
+    /// that the computer had to stop otherwise, or the error that stopped the program.
+    fn execute(&mut self) -> Result<Option<RunState<W>>, IntcodeError<W>> {
+        if self.ins_pointer >= self.memory.len() {
+            self.halted = true;
+            return Ok(Some(RunState::Halted));
+        }
+
+        // p1 is only valid if the num of args is 1
+        // p1+p2 are only valid if the num of args is 2
+        // p1+p2+p3 are only valid if the num of args is 3
+        let decoded = if self.predecoding {
+            self.memory.decoded(self.ins_pointer)
+        } else {
+            None
+        };
+        let (opcode, p1, p2, p3) = match decoded {
+            Some(decoded) => (
+                decoded.opcode as isize,
+                decoded.modes[0],
+                decoded.modes[1],
+                decoded.modes[2],
+            ),
+            // This is an invalid instruction, or one that hasn't been decoded ahead of time.
+            None => interpret_opcode(self.ins_pointer, self.memory.get(self.ins_pointer))?,
+        };
+        if opcode == HALT {
+            self.halted = true;
+            return Ok(Some(RunState::Halted));
+        }
+
+        // `interpret_opcode` already made sure that the opcode is valid.
+        let num_args = get_args_needed(opcode).unwrap();
+
+        // 1 argument needed
+        let v1 = self.get_value(1, p1)?;
+
+        if num_args == 1 {
+            match opcode {
+                INPUT => {
+                    let Some(input) = self.stdin.front().copied() else {
+                        return Ok(Some(RunState::NeedsInput));
+                    };
+
+                    self.set_value(1, input, p1)?;
+                    self.stdin.pop_front();
+                }
+                OUTPUT => {
+                    self.stdout.push(v1);
+                    self.ins_pointer += 2;
+                    return Ok(Some(RunState::Output(v1)));
+                }
+                RELATIVE => {
+                    self.relative_base = self.checked(self.relative_base.checked_add(v1))?;
+                }
+                _ => unreachable!("opcode {opcode} doesn't take 1 argument"),
+            };
+
+            self.ins_pointer += 2;
+            return Ok(self.take_watchpoint_hit());
+        }
+
+        // 2 arguments needed
+        let v2 = self.get_value(2, p2)?;
+        if num_args == 2 {
+            let should_jump = match opcode {
+                JMP_IF_TRUE => v1 != W::ZERO,
+                JMP_IF_FALSE => v1 == W::ZERO,
+                _ => unreachable!("opcode {opcode} doesn't take 2 arguments"),
+            };
+
+            if should_jump {
+                self.ins_pointer = self.to_address(v2)?;
+            } else {
+                self.ins_pointer += 3;
+            }
+
+            return Ok(None);
+        }
+
+        // 3 arguments needed, note that the third argument will implicitly be
+        // used by `set_value`, since the third argument tells us where to put
+        // the result of the operation.
+        match opcode {
+            ADD => self.set_value(3, self.checked(v1.checked_add(v2))?, p3)?,
+            MULTIPLY => self.set_value(3, self.checked(v1.checked_mul(v2))?, p3)?,
+            LESS_THAN => self.set_value(3, if v1 < v2 { W::ONE } else { W::ZERO }, p3)?,
+            EQUALS => self.set_value(3, if v1 == v2 { W::ONE } else { W::ZERO }, p3)?,
+            _ => unreachable!("opcode {opcode} doesn't take 3 arguments"),
+        };
+
+        self.ins_pointer += 4;
+        Ok(self.take_watchpoint_hit())
+    }
+
+    /// Gets the instruction pointer.
+    ///
+    /// # Returns
+    /// The address of the next instruction to execute.
+    pub fn instruction_pointer(&self) -> usize {
+        self.ins_pointer
+    }
+
+    /// Sets whether the computer uses instructions that were decoded ahead of time, which is on
+    /// by default. With it off, every instruction is decoded as it's executed, so this is only
+    /// useful for measuring how much decoding ahead of time helps.
+    ///
+    /// # Parameters
+    /// - `enabled`: Whether to use instructions that were decoded ahead of time.
+    pub fn set_predecoding(&mut self, enabled: bool) {
+        self.predecoding = enabled;
+    }
+
+    /// Moves the instruction pointer, e.g., to skip or repeat instructions while debugging.
+    /// Running the computer afterwards stops at a breakpoint at the new address, even if the
+    /// computer last stopped there.
+    ///
+    /// # Parameters
+    /// - `address`: The address of the next instruction to execute.
+    pub fn set_instruction_pointer(&mut self, address: usize) {
+        self.ins_pointer = address;
+        self.paused_at = None;
+    }
+
+    /// Gets the relative base.
+    ///
+    /// # Returns
+    /// The relative base.
+    pub fn relative_base(&self) -> W {
+        self.relative_base
+    }
+
+    /// Adds a breakpoint, so that running the computer stops right before the instruction at
+    /// this address is executed.
+    ///
+    /// # Parameters
+    /// - `address`: The address of the instruction.
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    /// Removes a breakpoint added by [`IntCodeComputer::add_breakpoint`].
+    ///
+    /// # Parameters
+    /// - `address`: The address of the instruction.
+    ///
+    /// # Returns
+    /// `true` if there was a breakpoint at this address and `false` otherwise.
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    /// Adds a breakpoint, so that running the computer stops right before any instruction with
+    /// this opcode is executed.
+    ///
+    /// # Parameters
+    /// - `opcode`: The opcode, without the parameter modes (e.g., `3` for input).
+    pub fn add_opcode_breakpoint(&mut self, opcode: isize) {
+        self.opcode_breakpoints.insert(opcode);
+    }
+
+    /// Removes a breakpoint added by [`IntCodeComputer::add_opcode_breakpoint`].
+    ///
+    /// # Parameters
+    /// - `opcode`: The opcode, without the parameter modes.
+    ///
+    /// # Returns
+    /// `true` if there was a breakpoint for this opcode and `false` otherwise.
+    pub fn remove_opcode_breakpoint(&mut self, opcode: isize) -> bool {
+        self.opcode_breakpoints.remove(&opcode)
+    }
+
+    /// Adds a watchpoint, so that running or stepping the computer stops right after an
+    /// instruction writes to this address, even if the value doesn't change.
+    ///
+    /// # Parameters
+    /// - `address`: The address to watch.
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    /// Removes a watchpoint added by [`IntCodeComputer::add_watchpoint`].
+    ///
+    /// # Parameters
+    /// - `address`: The watched address.
+    ///
+    /// # Returns
+    /// `true` if the address was watched and `false` otherwise.
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.remove(&address)
+    }
+
+    /// Whether there's a breakpoint for the instruction at the instruction pointer.
+    ///
+    /// # Returns
+    /// `true` if the computer should stop before this instruction and `false` otherwise.
+    fn at_breakpoint(&self) -> bool {
+        self.breakpoints.contains(&self.ins_pointer)
+            || self
+                .memory
+                .get_ref(self.ins_pointer)
+                .and_then(|raw| raw.to_isize())
+                .is_some_and(|raw| self.opcode_breakpoints.contains(&(raw % 100)))
+    }
+
+    /// Takes the write to a watched address made by the last instruction, if any.
+    ///
+    /// # Returns
+    /// The corresponding [`RunState::Watchpoint`], or `None` if no watched address was written.
+    fn take_watchpoint_hit(&mut self) -> Option<RunState<W>> {
+        self.watchpoint_hit
+            .take()
+            .map(|(address, old, new)| RunState::Watchpoint { address, old, new })
+    }
+
+    /// Runs the program until the next HALT instruction or an output is produced,
+    /// whichever one occurs first. The output is also added to the standard output.
+    ///
+    /// # Returns
+    /// Why the computer stopped running.
+    ///
+    /// # Panics
+    /// If the program can't be executed.
+    pub fn run_until_output(&mut self) -> RunState<W> {
+        self.run()
+    }
+
+    /// Runs the program until the next HALT instruction or the program pointer
+    /// points to the end of the program, whichever occurs first. If the program
+    /// needs input that isn't available, this stops early.
+    ///
+    /// # Returns
+    /// Either [`RunState::Halted`] or [`RunState::NeedsInput`], unless a breakpoint or
+    /// watchpoint stopped the computer.
+    ///
+    /// # Panics
+    /// If the program can't be executed. Use
+    /// [`IntCodeComputer::try_run_until_completion`] to handle this case.
+    pub fn run_until_completion(&mut self) -> RunState<W> {
+        self.try_run_until_completion()
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Runs the program until the next HALT instruction or the program pointer
+    /// points to the end of the program, whichever occurs first. This is like
+    /// [`IntCodeComputer::run_until_completion`], but returns an error instead of
+    /// panicking if the program can't be executed.
+    ///
+    /// # Returns
+    /// Either [`RunState::Halted`] or [`RunState::NeedsInput`] (unless a breakpoint or
+    /// watchpoint stopped the computer), or the error that stopped the program.
+    pub fn try_run_until_completion(&mut self) -> Result<RunState<W>, IntcodeError<W>> {
+        loop {
+            match self.try_run()? {
+                RunState::Output(_) => continue,
+                state => return Ok(state),
+            }
+        }
+    }
+
+    /// Whether the computer has halted.
+    ///
+    /// # Returns
+    /// `true` if the computer has halted and `false` otherwise.
+    pub fn has_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Converts a value used as an address by the current instruction into an index.
+    ///
+    /// # Parameters
+    /// - `address`: The address.
+    ///
+    /// # Returns
+    /// The index, or an error if the address is negative or larger than `isize::MAX`.
+    fn to_address(&self, address: W) -> Result<usize, IntcodeError<W>> {
+        let ins_pointer = self.ins_pointer;
+        let raw_opcode = self.memory.get(ins_pointer);
+        if address < W::ZERO {
+            return Err(IntcodeError::NegativeAddress {
+                ins_pointer,
+                raw_opcode,
+                address,
+            });
+        }
+
+        // An address up to `isize::MAX` fits in a `usize`, and one more than it does too.
+        address
+            .to_isize()
+            .map(|address| address as usize)
+            .ok_or(IntcodeError::AddressTooLarge {
+                ins_pointer,
+                raw_opcode,
+                address,
+            })
+    }
+
+    /// Checks the result of the current instruction's arithmetic.
+    ///
+    /// # Parameters
+    /// - `result`: The result, or `None` if it overflowed.
+    ///
+    /// # Returns
+    /// The result, or an error if it overflowed.
+    fn checked(&self, result: Option<W>) -> Result<W, IntcodeError<W>> {
+        result.ok_or_else(|| IntcodeError::Overflow {
+            ins_pointer: self.ins_pointer,
+            raw_opcode: self.memory.get(self.ins_pointer),
+        })
+    }
+
+    /// Gets the value at the specified offset, for the given mode type.
+    ///
+    /// If the offset results in an out of bounds error, this returns `0` instead.
+    ///
+    /// # Parameters
+    /// - `offset`: The offset of the parameter, from the instruction pointer.
+    /// - `mode_type`: The mode type for this parameter.
+    ///
+    /// # Returns
+    /// The value, or `0` if the index is not valid. If the parameter refers to an invalid
+    /// address, an error is returned.
+    fn get_value(&self, offset: usize, mode_type: ModeType) -> Result<W, IntcodeError<W>> {
+        let param = self.memory.get(self.ins_pointer + offset);
+        let address = match mode_type {
+            ModeType::Immediate => return Ok(param),
+            // For position, the parameter is the address of the value
+            ModeType::Position => param,
+            // For relative, adding the relative base to the parameter gives us the address
+            // of the value
+            ModeType::Relative => self.checked(self.relative_base.checked_add(param))?,
+        };
+
+        Ok(self.memory.get(self.to_address(address)?))
+    }
+
+    /// Sets the value `new_val` to the current program at the index specified by the value at
+    /// the current program's index at index `ins_pointer + offset`. In other words, this will
+    /// perform the operation
+    /// ```ignore
+    /// memory[memory[ins_pointer + offset]] = new_val
+    /// ```
+    ///
+    /// For example, if you specified `offset = 2`, then `ins_pointer = 25`, then this will
+    /// perform `memory[memory[25 + 2]] = new_val`.
+    ///
+    /// Note that this will implicitly require an additional argument. For example, with the
+    /// case of the `ADD` operation, while two arguments are needed to get the first (`v1`) and
+    /// second (`v2`) values, a "third" argument (usually indicated by `offset`, in our case
+    /// here this would be `offset = 3`) is needed to determine where the resulting value
+    /// `new_val = v1 + v2` should go.
+    ///
+    /// # Parameters
+    /// - `offset`: The offset of the parameter, from the instruction pointer.
+    /// - `new_val`: The new value.
+    /// - `mode_type`: The mode type. Note that giving immediate mode will result in an error.
+    ///
+    /// # Returns
+    /// An error if the parameter is in immediate mode or refers to an invalid address.
+    fn set_value(
+        &mut self,
+        offset: usize,
+        new_val: W,
+        mode_type: ModeType,
+    ) -> Result<(), IntcodeError<W>> {
+        // Parameters that an instruction writes to will never be in immediate mode.
+        let param = self.memory.get(self.ins_pointer + offset);
+        let address = match mode_type {
+            ModeType::Immediate => {
+                return Err(IntcodeError::ImmediateModeWrite {
+                    ins_pointer: self.ins_pointer,
+                    raw_opcode: self.memory.get(self.ins_pointer),
+                })
+            }
+            ModeType::Position => param,
+            ModeType::Relative => self.checked(self.relative_base.checked_add(param))?,
+        };
+
+        let idx = self.to_address(address)?;
+        if idx >= self.memory.len() {
+            self.halted = false;
+        }
+
+        if self.watchpoints.contains(&idx) {
+            self.watchpoint_hit = Some((idx, self.memory.get(idx), new_val));
+        }
+
+        self.memory.set(idx, new_val);
+        Ok(())
+    }
+
+    /// Gets a copy of the current program.
+    ///
+    /// # Returns
+    /// The current program.
+    ///
+    /// # Panics
+    /// If the memory is too long to copy. See [`IntCodeComputer::view_program`].
+    #[allow(dead_code)]
+    pub fn get_current_program(&self) -> Vec<W> {
+        self.copy_memory()
+    }
+}
+
+impl IntCodeComputer {
+    /// Runs the program like [`IntCodeComputer::try_run`], calling `trace` right before each
+    /// instruction is executed.
+    ///
+    /// # Parameters
+    /// - `trace`: The function to call with the computer and the decoded instruction that's
+    ///   about to be executed.
+    ///
+    /// # Returns
+    /// Why the computer stopped running, or the error that stopped the program.
+    pub fn try_run_with_trace<F>(&mut self, mut trace: F) -> Result<RunState, IntcodeError>
+    where
+        F: FnMut(&IntCodeComputer, &Instruction),
+    {
+        self.run_traced(Some(&mut |computer: &IntCodeComputer| {
+            trace(computer, &computer.current_instruction()?);
+            Ok(())
+        }))
+    }
+
+    /// Decodes the instruction at the instruction pointer. Unlike [`decode`], parameters past
+    /// the end of memory are read as `0`, like they would be when the instruction is executed.
+    ///
+    /// # Returns
+    /// The instruction, or an error if it can't be decoded.
+    pub fn current_instruction(&self) -> Result<Instruction, IntcodeError> {
+        disasm::decode_lenient(|address| self.memory.get(address), self.ins_pointer)
+    }
+}
+
+impl<W: Word> Index<usize> for IntCodeComputer<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.memory.get_ref(index).unwrap_or_else(|| {
+            panic!(
+                "address {index} is out of bounds for memory of length {}",
+                self.memory.len()
+            )
+        })
+    }
+}
+
+impl<W: Word> IndexMut<usize> for IntCodeComputer<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.memory.len();
+        assert!(
+            index < len,
+            "address {index} is out of bounds for memory of length {len}"
+        );
+        self.memory.get_mut(index)
+    }
+}
+
+/// Gets the number of arguments needed for this opcode.
+///
+/// # Parameters
+/// - `opcode`: The opcode.
+///
+/// # Returns
+/// The number of arguments needed, or `None` if the opcode is unknown.
+fn get_args_needed(opcode: isize) -> Option<usize> {
+    match opcode {
+        HALT => Some(0),
+        INPUT | OUTPUT | RELATIVE => Some(1),
+        JMP_IF_TRUE | JMP_IF_FALSE => Some(2),
+        ADD | MULTIPLY | LESS_THAN | EQUALS => Some(3),
+        _ => None,
+    }
+}
+
+/// Gets the opcode for a mnemonic used in listings, like `ADD` or `JT`.
+///
+/// # Parameters
+/// - `mnemonic`: The mnemonic, in any case.
+///
+/// # Returns
+/// The opcode, or `None` if there's no instruction with this mnemonic.
+pub fn opcode_for_mnemonic(mnemonic: &str) -> Option<isize> {
+    MNEMONICS
+        .iter()
+        .find(|(_, m)| m.eq_ignore_ascii_case(mnemonic))
+        .map(|(opcode, _)| *opcode)
+}
+
+/// Whether the instruction with this opcode writes its result to the address given by its last
+/// parameter.
+///
+/// # Parameters
+/// - `opcode`: The opcode.
+///
+/// # Returns
+/// `true` if the instruction writes to memory and `false` otherwise.
+fn writes_to_memory(opcode: isize) -> bool {
+    matches!(opcode, ADD | MULTIPLY | LESS_THAN | EQUALS | INPUT)
+}
+
+/// Interprets the opcode & parameter from the given value.
+///
+/// # Parameters
+/// - `ins_pointer`: The address of the instruction, used when reporting errors.
+/// - `raw_opcode`: The current opcode.
+///
+/// # Returns
+/// A tuple where
+/// - The first element is the opcode itself, and
+/// - The second element is the mode of the first parameter, and
+/// - The third element is the mode of the second parameter, and
+/// - The fourth element is the mode of the third parameter.
+///
+/// Modes of parameters that the instruction doesn't use are always position mode. If the
+/// opcode is unknown or a used parameter has an invalid mode, an error is returned instead.
+fn interpret_opcode<W: Word>(
+    ins_pointer: usize,
+    raw_opcode: W,
+) -> Result<(isize, ModeType, ModeType, ModeType), IntcodeError<W>> {
+    let unknown = IntcodeError::UnknownOpcode {
+        ins_pointer,
+        raw_opcode,
+    };
+    let Some(value) = raw_opcode
+        .to_isize()
+        .filter(|value| (0..100000).contains(value))
+    else {
+        return Err(unknown);
+    };
+
+    let opcode = value % 100;
+    let num_args = get_args_needed(opcode).ok_or(unknown)?;
+
+    // Only the modes of the parameters that the instruction uses need to be valid.
+    let mut modes = [ModeType::Position; 3];
+    let mut mode_digits = value / 100;
+    for mode in modes.iter_mut().take(num_args) {
+        *mode = ModeType::try_from(mode_digits % 10).map_err(|mode| {
+            IntcodeError::InvalidParameterMode {
+                ins_pointer,
+                raw_opcode,
+                mode,
+            }
+        })?;
+        mode_digits /= 10;
+    }
+
+    Ok((opcode, modes[0], modes[1], modes[2]))
+}
+
+/// Parses a string containing an Intcode program.
+///
+/// # Parameters
+/// - `code`: The Intcode program.
+///
+/// # Returns
+/// The parsed `Intcode` program.
+pub fn parse_intcode(code: &str) -> Vec<isize> {
+    parse_intcode_as(code)
+}
+
+/// Parses a string containing an Intcode program, for a computer that uses the given word type
+/// (e.g., `parse_intcode_as::<i128>(code)`).
+///
+/// # Parameters
+/// - `code`: The Intcode program.
+///
+/// # Returns
+/// The parsed `Intcode` program.
+///
+/// # Panics
+/// If a value isn't a valid number of the word type.
+pub fn parse_intcode_as<W: Word>(code: &str) -> Vec<W> {
+    code.split(',')
+        .map(|x| {
+            x.trim()
+                .parse::<W>()
+                .unwrap_or_else(|_| panic!("invalid Intcode value \"{x}\""))
+        })
+        .collect::<Vec<_>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::{
+        assemble, interpret_opcode, parse_intcode, parse_intcode_as, IntCodeComputer, IntcodeError,
+        ModeType, RunState, MULTIPLY,
+    };
+
+    #[test]
+    pub fn test_interpret_opcode_param() {
+        let (opcode, p1, p2, p3) = interpret_opcode(0, 1002).unwrap();
+        assert_eq!(MULTIPLY, opcode);
+        assert_eq!(ModeType::Position, p1);
+        assert_eq!(ModeType::Immediate, p2);
+        assert_eq!(ModeType::Position, p3);
+    }
+
+    #[test]
+    pub fn intcode2_test_1() {
+        let program = parse_intcode("1,0,0,0,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!([2, 0, 0, 0, 99].as_slice(), c.get_current_program());
+    }
+
+    #[test]
+    pub fn intcode2_test_2() {
+        let program = parse_intcode("2,3,0,3,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!([2, 3, 0, 6, 99].as_slice(), c.get_current_program());
+    }
+
+    #[test]
+    pub fn intcode2_test_3() {
+        let program = parse_intcode("2,4,4,5,99,0");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!([2, 4, 4, 5, 99, 9801].as_slice(), c.get_current_program());
+    }
+
+    #[test]
+    pub fn intcode2_test_4() {
+        let program = parse_intcode("1,1,1,4,99,5,6,0,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!(
+            [30, 1, 1, 4, 2, 5, 6, 0, 99].as_slice(),
+            c.get_current_program()
+        );
+    }
+
+    #[test]
+    pub fn intcode5_test_multiply_mixed() {
+        let program = parse_intcode("1002,4,3,4,33");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!([1002, 4, 3, 4, 99].as_slice(), c.get_current_program());
+    }
+
+    #[test]
+    pub fn intcode5_test_negative() {
+        let program = parse_intcode("1101,100,-1,4,0");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!([1101, 100, -1, 4, 99].as_slice(), c.get_current_program());
+    }
+
+    #[test]
+    pub fn intcode5_test_output_input() {
+        let program = parse_intcode("3,0,4,0,99");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        c.input_to_stdin(15);
+        c.run_until_completion();
+        assert!(!c.view_stdout().is_empty());
+        assert_eq!(15, c.view_stdout()[0]);
+    }
+
+    #[test]
+    pub fn intcode5_test_equal_position() {
+        let program = parse_intcode("3,9,8,9,10,9,4,9,99,-1,8");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, 125, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 8, 1);
+        test_stdin_stdout_intcode_helper(&mut c, 3, 0);
+        test_stdin_stdout_intcode_helper(&mut c, -8, 0);
+    }
+
+    #[test]
+    pub fn intcode5_test_less_position() {
+        let program = parse_intcode("3,9,7,9,10,9,4,9,99,-1,8");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, 15, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 8, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 1, 1);
+        test_stdin_stdout_intcode_helper(&mut c, -8, 1);
+    }
+
+    #[test]
+    pub fn intcode5_test_equal_immediate() {
+        let program = parse_intcode("3,3,1108,-1,8,3,4,3,99");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, 125, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 8, 1);
+        test_stdin_stdout_intcode_helper(&mut c, 3, 0);
+        test_stdin_stdout_intcode_helper(&mut c, -8, 0);
+    }
+
+    #[test]
+    pub fn intcode5_test_less_immediate() {
+        let program = parse_intcode("3,3,1107,-1,8,3,4,3,99");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, 15, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 8, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 1, 1);
+        test_stdin_stdout_intcode_helper(&mut c, -8, 1);
+    }
+
+    #[test]
+    pub fn intcode5_test_jump_position() {
+        let program = parse_intcode("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, 0, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 1, 1);
+        test_stdin_stdout_intcode_helper(&mut c, 2, 1);
+        test_stdin_stdout_intcode_helper(&mut c, -1, 1);
+        test_stdin_stdout_intcode_helper(&mut c, -2, 1);
+    }
+
+    #[test]
+    pub fn intcode5_test_jump_immediate() {
+        let program = parse_intcode("3,3,1105,-1,9,1101,0,0,12,4,12,99,1");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, 0, 0);
+        test_stdin_stdout_intcode_helper(&mut c, 1, 1);
+        test_stdin_stdout_intcode_helper(&mut c, 2, 1);
+        test_stdin_stdout_intcode_helper(&mut c, -1, 1);
+        test_stdin_stdout_intcode_helper(&mut c, -2, 1);
+    }
+
+    #[test]
+    pub fn intcode5_test_complex() {
+        let program = parse_intcode(
+            "3,21,1008,21,8,20,1005,20,22,107,8,21,20,\
+        1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,\
+        1,20,4,20,1105,1,46,98,99",
+        );
+        let mut c = IntCodeComputer::new(&program, None);
+
+        test_stdin_stdout_intcode_helper(&mut c, -5, 999);
+        test_stdin_stdout_intcode_helper(&mut c, 6, 999);
+        test_stdin_stdout_intcode_helper(&mut c, 0, 999);
+        test_stdin_stdout_intcode_helper(&mut c, 8, 1000);
+        test_stdin_stdout_intcode_helper(&mut c, 9, 1001);
+        test_stdin_stdout_intcode_helper(&mut c, 11, 1001);
+        test_stdin_stdout_intcode_helper(&mut c, 99, 1001);
+    }
+
+    #[test]
+    pub fn intcode9_test_relative_basic() {
+        let program = parse_intcode("109,19");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.relative_base = 2000;
+        c.run_until_completion();
+        assert_eq!(2019, c.relative_base);
+        assert!(c.has_halted());
+    }
+
+    #[test]
+    pub fn intcode9_test_relative_ex1() {
+        let program = parse_intcode("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!(program, c.view_stdout());
+        assert!(c.has_halted());
+    }
+
+    #[test]
+    pub fn intcode9_test_relative_ex2() {
+        let program = parse_intcode("1102,34915192,34915192,7,4,7,99,0");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!(16, c.view_stdout().last().unwrap().to_string().len());
+        assert!(c.has_halted());
+    }
+
+    #[test]
+    pub fn intcode9_test_relative_ex3() {
+        let program = parse_intcode("104,1125899906842624,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!(1125899906842624, *c.view_stdout().last().unwrap());
+        assert!(c.has_halted());
+    }
+
+    #[test]
+    pub fn intcode_test_run_needs_input() {
+        // Adds two numbers given as input, and outputs the result.
+        let program = parse_intcode("3,11,3,12,1,11,12,13,4,13,99,0,0,0");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        assert_eq!(RunState::NeedsInput, c.run());
+        assert_eq!(RunState::NeedsInput, c.run());
+        c.input_to_stdin(20);
+        assert_eq!(RunState::NeedsInput, c.run_until_completion());
+        assert!(!c.has_halted());
+        c.input_to_stdin(22);
+        assert_eq!(RunState::Output(42), c.run());
+        assert_eq!(RunState::Halted, c.run());
+        assert!(c.has_halted());
+        assert_eq!([42].as_slice(), c.view_stdout());
+    }
+
+    #[test]
+    pub fn intcode_test_run_outputs() {
+        let program = parse_intcode("104,1,104,2,104,3,99");
+        let mut c = IntCodeComputer::new(&program, None);
+
+        assert_eq!(RunState::Output(1), c.run_until_output());
+        assert_eq!(RunState::Output(2), c.run());
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!([1, 2, 3].as_slice(), c.view_stdout());
+    }
+
+    #[test]
+    pub fn intcode_test_interpret_opcode_errors() {
+        assert_eq!(
+            Err(IntcodeError::UnknownOpcode {
+                ins_pointer: 5,
+                raw_opcode: 42
+            }),
+            interpret_opcode(5, 42)
+        );
+        assert_eq!(
+            Err(IntcodeError::UnknownOpcode {
+                ins_pointer: 0,
+                raw_opcode: -1
+            }),
+            interpret_opcode(0, -1)
+        );
+        assert_eq!(
+            Err(IntcodeError::InvalidParameterMode {
+                ins_pointer: 0,
+                raw_opcode: 1301,
+                mode: 3
+            }),
+            interpret_opcode(0, 1301)
+        );
+        // The third parameter of JMP_IF_TRUE is never used, so its mode doesn't matter.
+        assert!(interpret_opcode(0, 31105).is_ok());
+    }
+
+    #[test]
+    pub fn intcode_test_errors() {
+        let cases = [
+            (
+                "1,0,0,0,42",
+                IntcodeError::UnknownOpcode {
+                    ins_pointer: 4,
+                    raw_opcode: 42,
+                },
+            ),
+            (
+                "30001,0,0,0,99",
+                IntcodeError::InvalidParameterMode {
+                    ins_pointer: 0,
+                    raw_opcode: 30001,
+                    mode: 3,
+                },
+            ),
+            (
+                "11101,1,2,3,99",
+                IntcodeError::ImmediateModeWrite {
+                    ins_pointer: 0,
+                    raw_opcode: 11101,
+                },
+            ),
+            (
+                "1,-5,0,0,99",
+                IntcodeError::NegativeAddress {
+                    ins_pointer: 0,
+                    raw_opcode: 1,
+                    address: -5,
+                },
+            ),
+            (
+                "109,-10,203,3,99",
+                IntcodeError::NegativeAddress {
+                    ins_pointer: 2,
+                    raw_opcode: 203,
+                    address: -7,
+                },
+            ),
+            (
+                "1105,1,-3,99",
+                IntcodeError::NegativeAddress {
+                    ins_pointer: 0,
+                    raw_opcode: 1105,
+                    address: -3,
+                },
+            ),
+        ];
+
+        for (program, expected) in cases {
+            let mut c = IntCodeComputer::new(&parse_intcode(program), Some(vec![1]));
+            assert_eq!(Err(expected), c.try_run(), "{program}");
+            // The computer should stay on the offending instruction.
+            assert_eq!(Err(expected), c.try_run_until_completion(), "{program}");
+            assert!(!c.has_halted());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "instruction 42 at address 4: unknown opcode")]
+    pub fn intcode_test_run_panics_on_error() {
+        let mut c = IntCodeComputer::new(&parse_intcode("1,0,0,0,42"), None);
+        c.run_until_completion();
+    }
+
+    #[test]
+    pub fn intcode_test_relative_stack() {
+        let program = assemble(
+            "
+                ARB #stack
+                IN -> [rb]
+                IN -> [rb+1]
+                IN -> [rb+2]
+                ADD [rb], [rb+1] -> [rb+3]
+                ADD [rb+3], [rb+2] -> [rb+3]
+                OUT [rb+3]
+                ARB #-1
+                OUT [rb+4]      ; the same value, now relative to the moved base
+                HALT
+            stack: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, Some(vec![2, 3, 4]));
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!([9, 9].as_slice(), c.view_stdout());
+        // The stack grew past the end of the program.
+        assert_eq!(program.len() + 3, c.get_current_program().len());
+    }
+
+    #[test]
+    pub fn intcode_test_reset_relative() {
+        // Outputs the value at relative address 0, then moves the relative base.
+        let program = assemble("OUT [rb]\nARB #3\nHALT").unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!([program[0]].as_slice(), c.view_stdout());
+        assert_eq!(3, c.relative_base());
+
+        c.reset();
+        assert_eq!(0, c.relative_base());
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!([program[0]].as_slice(), c.view_stdout());
+    }
+
+    #[test]
+    pub fn intcode_test_memory_growth() {
+        let program = assemble(
+            "
+                ADD #41, #1 -> [1000]
+                OUT [1000]
+                OUT [2000]      ; memory that was never written to reads as 0
+                HALT
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        assert_eq!([42, 0].as_slice(), c.view_stdout());
+        assert_eq!(1001, c.get_current_program().len());
+    }
+
+    #[test]
+    pub fn intcode_test_echo_until_zero() {
+        let program = assemble(
+            "
+            loop: IN -> [value]
+                JF [value], #end
+                OUT [value]
+                JT #1, #loop
+            end: HALT
+            value: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, Some(vec![5]));
+        assert_eq!(RunState::Output(5), c.run());
+        assert_eq!(RunState::NeedsInput, c.run());
+        assert_eq!(RunState::NeedsInput, c.run());
+        c.input_to_stdin(7);
+        assert_eq!(RunState::Output(7), c.run());
+        c.input_to_stdin(0);
+        assert_eq!(RunState::Halted, c.run());
+        assert_eq!([5, 7].as_slice(), c.view_stdout());
+    }
+
+    #[test]
+    pub fn intcode_test_step() {
+        let program = assemble("ADD #1, #2 -> [7]\nOUT [7]\nHALT\nDATA 0").unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(None, c.step());
+        assert_eq!(4, c.instruction_pointer());
+        assert_eq!(Some(RunState::Output(3)), c.step());
+        assert_eq!(Some(RunState::Halted), c.step());
+        assert!(c.has_halted());
+    }
+
+    #[test]
+    pub fn intcode_test_breakpoints() {
+        let program = assemble(
+            "
+                ARB #5
+            loop: IN -> [value]
+                JF [value], #end
+            out: OUT [value]
+                JT #1, #loop
+            end: HALT
+            value: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, Some(vec![4]));
+        c.add_breakpoint(2);
+        c.add_opcode_breakpoint(4);
+
+        assert_eq!(RunState::Breakpoint(2), c.run());
+        assert_eq!(RunState::Breakpoint(7), c.run());
+        assert_eq!(RunState::Output(4), c.run());
+        assert_eq!(RunState::Breakpoint(2), c.run());
+        // Running again after the input instruction needed input doesn't stop at its
+        // breakpoint again.
+        assert_eq!(RunState::NeedsInput, c.run());
+        c.input_to_stdin(0);
+        assert!(c.remove_breakpoint(2));
+        assert!(!c.remove_breakpoint(2));
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!(5, c.relative_base());
+    }
+
+    #[test]
+    pub fn intcode_test_breakpoints_after_moving() {
+        let program = assemble(
+            "
+            loop: IN -> [value]
+                OUT [value]
+                JT #1, #loop
+            value: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, Some(vec![1, 2]));
+        c.add_breakpoint(0);
+        assert_eq!(RunState::Breakpoint(0), c.run());
+
+        // Moving away and back means that the breakpoint should be hit again.
+        c.set_instruction_pointer(2);
+        c.set_instruction_pointer(0);
+        assert_eq!(RunState::Breakpoint(0), c.run());
+
+        // Likewise for stepping back around to the breakpoint.
+        assert_eq!(None, c.step());
+        assert_eq!(Some(RunState::Output(1)), c.step());
+        assert_eq!(None, c.step());
+        assert_eq!(0, c.instruction_pointer());
+        assert_eq!(RunState::Breakpoint(0), c.run());
+        assert_eq!(RunState::Output(2), c.run());
+    }
+
+    #[test]
+    pub fn intcode_test_watchpoints() {
+        let program = assemble(
+            "
+                ADD #1, #2 -> [x]
+                MUL [x], #1 -> [x]
+                ADD [x], #1 -> [y]
+                HALT
+            x: DATA 0
+            y: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        c.add_watchpoint(13);
+        assert_eq!(
+            RunState::Watchpoint {
+                address: 13,
+                old: 0,
+                new: 3
+            },
+            c.run_until_completion()
+        );
+        assert_eq!(4, c.instruction_pointer());
+        assert_eq!(
+            Some(RunState::Watchpoint {
+                address: 13,
+                old: 3,
+                new: 3
+            }),
+            c.step()
+        );
+        assert!(c.remove_watchpoint(13));
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!(4, c[14]);
+    }
+
+    #[test]
+    pub fn intcode_test_trace() {
+        let program = parse_intcode("109,1,204,-1,1001,100,1,100,1008,100,3,101,1006,101,0,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        let mut trace = vec![];
+        let state = c
+            .try_run_with_trace(|computer, instruction| {
+                trace.push(format!("{instruction} rb={}", computer.relative_base()))
+            })
+            .unwrap();
+        assert_eq!(RunState::Output(109), state);
+        assert_eq!(vec!["0000: ARB #1 rb=0", "0002: OUT [rb-1] rb=1"], trace);
+
+        trace.clear();
+        c.try_run_with_trace(|_, instruction| trace.push(instruction.mnemonic().to_string()))
+            .unwrap();
+        assert_eq!(vec!["ADD", "EQ", "JF", "ARB", "OUT"], trace);
+    }
+
+    #[test]
+    pub fn intcode_test_self_modifying() {
+        // Changes `OUT #7` to `OUT [7]`.
+        let program = parse_intcode("1101,4,0,4,104,7,99,42");
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(RunState::Output(42), c.run());
+
+        // The same change, but made from outside the program.
+        let mut c = IntCodeComputer::new(&parse_intcode("104,3,99,42"), None);
+        c[0] = 4;
+        assert_eq!(RunState::Output(42), c.run());
+        c.reset();
+        assert_eq!(RunState::Output(3), c.run());
+    }
+
+    #[test]
+    pub fn intcode_test_snapshot() {
+        let program = assemble(
+            "
+            loop: IN -> [value]
+                ADD [value], [total] -> [total]
+                OUT [total]
+                JT #1, #loop
+            value: DATA 0
+            total: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, Some(vec![5]));
+        assert_eq!(RunState::Output(5), c.run());
+        let snapshot = c.snapshot();
+
+        c.input_to_stdin(3);
+        assert_eq!(RunState::Output(8), c.run());
+        assert_eq!(RunState::NeedsInput, c.run());
+
+        // Restoring can be done more than once, and isn't affected by later writes.
+        for input in [10, 20] {
+            c.restore(&snapshot);
+            c.input_to_stdin(input);
+            assert_eq!(RunState::Output(5 + input), c.run());
+        }
+
+        assert_eq!(25, c[program.len() - 1]);
+    }
+
+    #[test]
+    pub fn intcode_test_high_address() {
+        let program = assemble(
+            "
+                ADD #41, #1 -> [1099511627776]
+                OUT [1099511627776]
+                ARB #1099511627777
+                OUT [rb]
+                HALT
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!([42, 0].as_slice(), c.view_stdout());
+        assert_eq!((1 << 40) + 1, c.memory_len());
+        assert_eq!(42, c[1 << 40]);
+        assert_eq!(0, c[1 << 30]);
+    }
+
+    #[test]
+    pub fn intcode_test_view_high_memory() {
+        let program = assemble("ADD #7, #0 -> [1099511627776]\nHALT").unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(RunState::Halted, c.run_until_completion());
+        assert_eq!(vec![0, 7, 0], c.view_memory((1 << 40) - 1, 3));
+        assert_eq!(&program[..2], c.view_memory(0, 2).as_slice());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn intcode_test_view_program_too_long() {
+        let program = assemble("ADD #7, #0 -> [1099511627776]\nHALT").unwrap();
+        let mut c = IntCodeComputer::new(&program, None);
+        c.run_until_completion();
+        c.view_program();
+    }
+
+    #[test]
+    pub fn intcode_test_overflow() {
+        let program = vec![1101, isize::MAX, 1, 0, 99];
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(
+            Err(IntcodeError::Overflow {
+                ins_pointer: 0,
+                raw_opcode: 1101
+            }),
+            c.try_run()
+        );
+
+        let program = parse_intcode("1102,4611686018427387904,2,0,99");
+        let mut c = IntCodeComputer::new(&program, None);
+        assert!(matches!(c.try_run(), Err(IntcodeError::Overflow { .. })));
+
+        let program = vec![109, isize::MIN, 209, -1, 99];
+        let mut c = IntCodeComputer::new(&program, None);
+        assert_eq!(
+            Err(IntcodeError::Overflow {
+                ins_pointer: 2,
+                raw_opcode: 209
+            }),
+            c.try_run()
+        );
+        assert_eq!(isize::MIN, c.relative_base());
+    }
+
+    #[test]
+    pub fn intcode_test_i128() {
+        let code = "1102,9223372036854775807,4,7,4,7,99,0";
+        let mut c = IntCodeComputer::new(&parse_intcode_as::<i128>(code), None);
+        assert_eq!(RunState::Output(i64::MAX as i128 * 4), c.run());
+
+        let mut c = IntCodeComputer::new(&parse_intcode_as::<i64>(code), None);
+        assert_eq!(
+            "instruction 1102 at address 0: arithmetic overflow",
+            c.try_run().unwrap_err().to_string()
+        );
+
+        // Addresses still have to fit in an `isize`.
+        let program = vec![4, 1i128 << 70, 99];
+        assert_eq!(
+            Err(IntcodeError::AddressTooLarge {
+                ins_pointer: 0,
+                raw_opcode: 4,
+                address: 1 << 70
+            }),
+            IntCodeComputer::new(&program, None).try_run()
+        );
+    }
+
+    /// Helps test standard input/output of the Intcode computer.
+    ///
+    /// # Parameters
+    /// - `c`: The computer.
+    /// - `input`: The input.
+    /// - `expected`: The last expected output from standard output.
+    fn test_stdin_stdout_intcode_helper(c: &mut IntCodeComputer, input: isize, expected: isize) {
+        c.reset();
+        c.input_to_stdin(input);
+        c.run_until_completion();
+        assert!(!c.view_stdout().is_empty());
+        assert_eq!(expected, *c.view_stdout().last().unwrap());
+        assert!(c.has_halted());
+    }
+}
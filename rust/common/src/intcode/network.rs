@@ -0,0 +1,430 @@
+use super::{IntCodeComputer, IntcodeError, RunState};
+
+/// How a [`Network`] routes the output of its machines.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Routing {
+    /// Every output of machine `i` is added, in order, to the input of each machine listed in
+    /// the `i`th entry. For example, `Pipes(vec![vec![1], vec![0]])` connects two machines in a
+    /// feedback loop.
+    Pipes(Vec<Vec<usize>>),
+    /// Machines output packets as three values, `address, x, y`. A packet is added to the
+    /// input of the machine with that address (its index in the network); packets for any
+    /// other address are set aside (see [`Network::take_undelivered`]). If a machine takes its
+    /// turn with no packets waiting for it, it's given `idle_input` instead, if there is one.
+    Packets { idle_input: Option<isize> },
+}
+
+/// A packet sent between machines of a [`Network`] using [`Routing::Packets`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Packet {
+    /// The address that the packet is sent to.
+    address: isize,
+    /// The first value of the packet.
+    x: isize,
+    /// The second value of the packet.
+    y: isize,
+}
+
+impl Packet {
+    /// Creates a new packet.
+    ///
+    /// # Parameters
+    /// - `address`: The address that the packet is sent to.
+    /// - `x`: The first value of the packet.
+    /// - `y`: The second value of the packet.
+    ///
+    /// # Returns
+    /// The packet.
+    pub fn new(address: isize, x: isize, y: isize) -> Self {
+        Self { address, x, y }
+    }
+
+    /// Gets the address that the packet is sent to.
+    ///
+    /// # Returns
+    /// The address.
+    pub fn address(&self) -> isize {
+        self.address
+    }
+
+    /// Gets the first value of the packet.
+    ///
+    /// # Returns
+    /// The first value.
+    pub fn x(&self) -> isize {
+        self.x
+    }
+
+    /// Gets the second value of the packet.
+    ///
+    /// # Returns
+    /// The second value.
+    pub fn y(&self) -> isize {
+        self.y
+    }
+}
+
+/// The state of a [`Network`] after a round of execution.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum NetworkState {
+    /// At least one machine made progress, so running another round may do more.
+    Running,
+    /// No machine produced output, halted or had any input besides the idle input during the
+    /// round, so the machines are waiting on each other (or on input from outside).
+    Idle,
+    /// At least one machine stopped at a breakpoint or watchpoint. Running another round
+    /// resumes it.
+    Paused,
+    /// Every machine has halted.
+    Halted,
+}
+
+/// A group of Intcode machines that send their output to each other.
+///
+/// The machines are run in rounds. In each round, every machine that hasn't halted takes a turn in
+/// order of index, running until it needs input that isn't available, halts, or stops at a
+/// breakpoint or watchpoint. A machine that stopped at a breakpoint or watchpoint isn't given the
+/// idle input on its next turn, since it isn't waiting for input. Outputs are routed as soon as
+/// they're produced, so a machine can receive input from machines that took their turn earlier in
+/// the same round.
+pub struct Network {
+    /// The machines, indexed by address.
+    machines: Vec<IntCodeComputer>,
+    /// How the outputs of the machines are routed.
+    routing: Routing,
+    /// The values of the packet that each machine is in the middle of sending.
+    partial_packets: Vec<Vec<isize>>,
+    /// Packets that were sent to addresses outside of the network.
+    undelivered: Vec<Packet>,
+    /// Whether each machine stopped at a breakpoint or watchpoint on its last turn.
+    paused: Vec<bool>,
+}
+
+impl Network {
+    /// Creates a new network.
+    ///
+    /// # Parameters
+    /// - `machines`: The machines. Any input that the machines need to start (e.g., a phase
+    ///   setting or their own address) should already be in their input queues.
+    /// - `routing`: How the outputs of the machines are routed.
+    ///
+    /// # Returns
+    /// The network.
+    ///
+    /// # Panics
+    /// If `routing` uses pipes and doesn't have exactly one entry per machine, or refers to a
+    /// machine that doesn't exist.
+    pub fn new(machines: Vec<IntCodeComputer>, routing: Routing) -> Self {
+        if let Routing::Pipes(pipes) = &routing {
+            assert_eq!(
+                machines.len(),
+                pipes.len(),
+                "expected one pipe entry per machine"
+            );
+            assert!(
+                pipes.iter().flatten().all(|&to| to < machines.len()),
+                "pipe to a machine that doesn't exist"
+            );
+        }
+
+        Self {
+            partial_packets: vec![vec![]; machines.len()],
+            paused: vec![false; machines.len()],
+            machines,
+            routing,
+            undelivered: vec![],
+        }
+    }
+
+    /// Gets the number of machines in the network.
+    ///
+    /// # Returns
+    /// The number of machines.
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    /// Whether the network has no machines.
+    ///
+    /// # Returns
+    /// `true` if there are no machines and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    /// Gets the machine at the given index.
+    ///
+    /// # Parameters
+    /// - `idx`: The index (or address) of the machine.
+    ///
+    /// # Returns
+    /// The machine.
+    pub fn machine(&self, idx: usize) -> &IntCodeComputer {
+        &self.machines[idx]
+    }
+
+    /// Gets the machine at the given index, e.g., to give it input from outside the network.
+    ///
+    /// # Parameters
+    /// - `idx`: The index (or address) of the machine.
+    ///
+    /// # Returns
+    /// The machine.
+    pub fn machine_mut(&mut self, idx: usize) -> &mut IntCodeComputer {
+        &mut self.machines[idx]
+    }
+
+    /// Sends a packet, as if a machine had sent it.
+    ///
+    /// # Parameters
+    /// - `packet`: The packet. If its address isn't in the network, it's set aside with the
+    ///   other undelivered packets.
+    pub fn send(&mut self, packet: Packet) {
+        match usize::try_from(packet.address)
+            .ok()
+            .and_then(|address| self.machines.get_mut(address))
+        {
+            Some(machine) => {
+                machine.input_to_stdin(packet.x);
+                machine.input_to_stdin(packet.y);
+            }
+            None => self.undelivered.push(packet),
+        }
+    }
+
+    /// Takes the packets that were sent to addresses outside of the network, like a NAT or a
+    /// final answer.
+    ///
+    /// # Returns
+    /// The undelivered packets, in the order that they were sent.
+    pub fn take_undelivered(&mut self) -> Vec<Packet> {
+        std::mem::take(&mut self.undelivered)
+    }
+
+    /// Runs one round, giving every machine that hasn't halted a turn.
+    ///
+    /// # Returns
+    /// The state of the network after the round, or the first error raised by a machine. If a
+    /// machine raises an error, the remaining machines don't get a turn. If any machine stopped
+    /// at a breakpoint or watchpoint, the state is [`NetworkState::Paused`].
+    pub fn run_round(&mut self) -> Result<NetworkState, IntcodeError> {
+        let mut active = false;
+        for idx in 0..self.machines.len() {
+            if self.machines[idx].has_halted() {
+                continue;
+            }
+
+            let was_paused = std::mem::take(&mut self.paused[idx]);
+            if !self.machines[idx].stdin.is_empty() {
+                active = true;
+            } else if let Routing::Packets {
+                idle_input: Some(idle_input),
+            } = self.routing
+            {
+                if !was_paused {
+                    self.machines[idx].input_to_stdin(idle_input);
+                }
+            }
+
+            loop {
+                match self.machines[idx].try_run()? {
+                    RunState::Output(value) => {
+                        active = true;
+                        self.route(idx, value);
+                    }
+                    RunState::Halted => {
+                        active = true;
+                        break;
+                    }
+                    RunState::NeedsInput => break,
+                    RunState::Breakpoint(_) | RunState::Watchpoint { .. } => {
+                        self.paused[idx] = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(if self.machines.iter().all(|m| m.has_halted()) {
+            NetworkState::Halted
+        } else if self.paused.contains(&true) {
+            NetworkState::Paused
+        } else if active {
+            NetworkState::Running
+        } else {
+            NetworkState::Idle
+        })
+    }
+
+    /// Runs rounds until the network is idle, a machine stops at a breakpoint or watchpoint, or
+    /// every machine has halted.
+    ///
+    /// # Returns
+    /// Either [`NetworkState::Idle`], [`NetworkState::Paused`] or [`NetworkState::Halted`], or
+    /// the first error raised by a machine.
+    pub fn run_until_idle(&mut self) -> Result<NetworkState, IntcodeError> {
+        loop {
+            match self.run_round()? {
+                NetworkState::Running => continue,
+                state => return Ok(state),
+            }
+        }
+    }
+
+    /// Routes a value that a machine output.
+    ///
+    /// # Parameters
+    /// - `from`: The index of the machine.
+    /// - `value`: The value.
+    fn route(&mut self, from: usize, value: isize) {
+        match &self.routing {
+            Routing::Pipes(pipes) => {
+                for &to in &pipes[from] {
+                    self.machines[to].input_to_stdin(value);
+                }
+            }
+            Routing::Packets { .. } => {
+                self.partial_packets[from].push(value);
+                if let [address, x, y] = self.partial_packets[from][..] {
+                    self.partial_packets[from].clear();
+                    self.send(Packet::new(address, x, y));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::{
+        assemble, parse_intcode, IntCodeComputer, Network, NetworkState, Packet, Routing,
+    };
+
+    #[test]
+    pub fn test_feedback_loop() {
+        // The first example of 2019 day 7 part 2.
+        let program = parse_intcode(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,\
+            0,5",
+        );
+        let machines = [9, 8, 7, 6, 5]
+            .into_iter()
+            .map(|phase| IntCodeComputer::new(&program, Some(vec![phase])))
+            .collect();
+        let mut network = Network::new(
+            machines,
+            Routing::Pipes(vec![vec![1], vec![2], vec![3], vec![4], vec![0]]),
+        );
+        network.machine_mut(0).input_to_stdin(0);
+
+        assert_eq!(Ok(NetworkState::Halted), network.run_until_idle());
+        assert_eq!(Some(&139629729), network.machine(4).view_stdout().last());
+    }
+
+    #[test]
+    pub fn test_pipes_idle() {
+        // Each machine forwards what it reads, so nothing happens until there's some input.
+        let program = assemble("loop: IN -> [x]\nOUT [x]\nJT #1, #loop\nx: DATA 0").unwrap();
+        let machines = (0..3)
+            .map(|_| IntCodeComputer::new(&program, None))
+            .collect();
+        let mut network = Network::new(machines, Routing::Pipes(vec![vec![1], vec![2], vec![]]));
+        assert_eq!(Ok(NetworkState::Idle), network.run_round());
+
+        network.machine_mut(0).input_to_stdin(7);
+        assert_eq!(Ok(NetworkState::Running), network.run_round());
+        assert_eq!(Ok(NetworkState::Idle), network.run_round());
+        assert_eq!([7].as_slice(), network.machine(2).view_stdout());
+    }
+
+    #[test]
+    pub fn test_packets() {
+        // Machine 0 sends (10, 20) to machine 1, which sends their sum and its address to 255.
+        let program = assemble(
+            "
+                IN -> [addr]
+                JT [addr], #recv
+                OUT #1
+                OUT #10
+                OUT #20
+            recv: IN -> [x]
+                EQ [x], #-1 -> [tmp]
+                JT [tmp], #recv
+                IN -> [y]
+                ADD [x], [y] -> [sum]
+                OUT #255
+                OUT [sum]
+                OUT [addr]
+                JT #1, #recv
+            addr: DATA 0
+            x: DATA 0
+            y: DATA 0
+            tmp: DATA 0
+            sum: DATA 0
+            ",
+        )
+        .unwrap();
+        let machines = (0..2)
+            .map(|addr| IntCodeComputer::new(&program, Some(vec![addr])))
+            .collect();
+        let mut network = Network::new(
+            machines,
+            Routing::Packets {
+                idle_input: Some(-1),
+            },
+        );
+
+        assert_eq!(Ok(NetworkState::Running), network.run_round());
+        assert_eq!(vec![Packet::new(255, 30, 1)], network.take_undelivered());
+        assert_eq!(Ok(NetworkState::Idle), network.run_round());
+        assert!(network.take_undelivered().is_empty());
+
+        // Packets can also come from outside the network.
+        network.send(Packet::new(1, 1, 2));
+        network.send(Packet::new(-3, 0, 0));
+        assert_eq!(Ok(NetworkState::Idle), network.run_until_idle());
+        assert_eq!(
+            vec![Packet::new(-3, 0, 0), Packet::new(255, 3, 1)],
+            network.take_undelivered()
+        );
+    }
+
+    #[test]
+    pub fn test_paused() {
+        // Machine 0 sends a packet to 255 every time it's given a value other than -1.
+        let program = assemble(
+            "
+            loop: IN -> [x]
+                EQ [x], #-1 -> [tmp]
+                JT [tmp], #loop
+            send: OUT #255
+                OUT [x]
+                OUT #0
+                JT #1, #loop
+            x: DATA 0
+            tmp: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut machine = IntCodeComputer::new(&program, None);
+        machine.add_breakpoint(9);
+        let mut network = Network::new(
+            vec![machine],
+            Routing::Packets {
+                idle_input: Some(-1),
+            },
+        );
+        assert_eq!(Ok(NetworkState::Idle), network.run_round());
+
+        // A paused machine isn't idle, and isn't given idle input while it's paused.
+        network.send(Packet::new(0, 5, 6));
+        assert_eq!(Ok(NetworkState::Paused), network.run_until_idle());
+        assert_eq!(1, network.machine(0).stdin.len());
+        assert_eq!(Ok(NetworkState::Paused), network.run_round());
+        assert_eq!(vec![Packet::new(255, 5, 0)], network.take_undelivered());
+        assert!(network.machine(0).stdin.is_empty());
+        assert_eq!(Ok(NetworkState::Running), network.run_round());
+        assert_eq!(vec![Packet::new(255, 6, 0)], network.take_undelivered());
+        assert_eq!(Ok(NetworkState::Idle), network.run_round());
+    }
+}
@@ -0,0 +1,381 @@
+use super::{memory::Memory, IntCodeComputer, Word};
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// The first line of a saved snapshot.
+const HEADER: &str = "intcode-snapshot 1";
+
+/// A saved state of an [`IntCodeComputer`]'s execution, which can be restored to go back to
+/// that point of the program. Taking or restoring a snapshot doesn't copy the computer's
+/// memory; memory is only copied, a page at a time, when the computer writes to it.
+///
+/// Snapshots can also be saved to and loaded from a simple line-based text format, e.g., to
+/// continue a long run later:
+/// ```text
+/// intcode-snapshot 1
+/// ip 4
+/// rb 0
+/// halted false
+/// stdin 1,2
+/// stdout 3
+/// len 2001
+/// mem 0 1101,4,0,4,104,7,99,42
+/// mem 2000 5
+/// ```
+/// Each `mem` line holds values starting at the given address; memory that isn't listed is
+/// zero.
+#[derive(Clone)]
+pub struct Snapshot<W = isize> {
+    /// The memory.
+    memory: Memory<W>,
+    /// The instruction pointer.
+    ins_pointer: usize,
+    /// The relative base.
+    relative_base: W,
+    /// Whether the computer is halted.
+    halted: bool,
+    /// The input queue.
+    stdin: VecDeque<W>,
+    /// The output so far.
+    stdout: Vec<W>,
+}
+
+impl<W: Word> Snapshot<W> {
+    /// Writes the snapshot in its text format.
+    ///
+    /// # Parameters
+    /// - `writer`: Where to write the snapshot to.
+    ///
+    /// # Returns
+    /// An error if the snapshot couldn't be written.
+    pub fn write_to<T: Write>(&self, mut writer: T) -> io::Result<()> {
+        writeln!(writer, "{HEADER}")?;
+        writeln!(writer, "ip {}", self.ins_pointer)?;
+        writeln!(writer, "rb {}", self.relative_base)?;
+        writeln!(writer, "halted {}", self.halted)?;
+        writeln!(
+            writer,
+            "{}",
+            format!("stdin {}", join(&self.stdin)).trim_end()
+        )?;
+        writeln!(
+            writer,
+            "{}",
+            format!("stdout {}", join(&self.stdout)).trim_end()
+        )?;
+        writeln!(writer, "len {}", self.memory.len())?;
+        for (address, values) in self.memory.segments() {
+            writeln!(writer, "mem {address} {}", join(values))?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads a snapshot written by [`Snapshot::write_to`].
+    ///
+    /// # Parameters
+    /// - `reader`: Where to read the snapshot from.
+    ///
+    /// # Returns
+    /// The snapshot, or an error if it couldn't be read. If the text isn't a valid snapshot,
+    /// the error is of kind [`io::ErrorKind::InvalidData`].
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(HEADER) {
+            return Err(invalid(1, format!("expected \"{HEADER}\"")));
+        }
+
+        let mut ins_pointer = None;
+        let mut relative_base = None;
+        let mut halted = None;
+        let mut stdin = VecDeque::new();
+        let mut stdout = vec![];
+        let mut memory: Option<Memory<W>> = None;
+        for (idx, line) in lines.enumerate() {
+            let line_num = idx + 2;
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, rest) = line.split_once(' ').unwrap_or((&line, ""));
+            match key {
+                "ip" => ins_pointer = Some(parse(line_num, rest)?),
+                "rb" => relative_base = Some(parse(line_num, rest)?),
+                "halted" => halted = Some(parse(line_num, rest)?),
+                "stdin" => stdin = parse_values(line_num, rest)?.into(),
+                "stdout" => stdout = parse_values(line_num, rest)?,
+                "len" => memory = Some(Memory::zeroed(parse(line_num, rest)?)),
+                "mem" => {
+                    let Some(memory) = memory.as_mut() else {
+                        return Err(invalid(line_num, "\"mem\" before \"len\""));
+                    };
+
+                    let (address, values) = rest.split_once(' ').unwrap_or((rest, ""));
+                    let address: usize = parse(line_num, address)?;
+                    let values = parse_values(line_num, values)?;
+                    if address.saturating_add(values.len()) > memory.len() {
+                        return Err(invalid(line_num, "values past the end of memory"));
+                    }
+
+                    for (i, value) in values.into_iter().enumerate() {
+                        memory.set(address + i, value);
+                    }
+                }
+                _ => return Err(invalid(line_num, format!("unknown key \"{key}\""))),
+            }
+        }
+
+        let missing = |key| invalid(0, format!("missing \"{key}\""));
+        Ok(Self {
+            memory: memory.ok_or_else(|| missing("len"))?,
+            ins_pointer: ins_pointer.ok_or_else(|| missing("ip"))?,
+            relative_base: relative_base.ok_or_else(|| missing("rb"))?,
+            halted: halted.ok_or_else(|| missing("halted"))?,
+            stdin,
+            stdout,
+        })
+    }
+}
+
+impl<W: Word> IntCodeComputer<W> {
+    /// Saves the state of the computer's execution, i.e., its memory, instruction pointer,
+    /// relative base, input and output queues, and whether it has halted.
+    ///
+    /// # Returns
+    /// The snapshot, which can be given to [`IntCodeComputer::restore`].
+    pub fn snapshot(&self) -> Snapshot<W> {
+        Snapshot {
+            memory: self.memory.clone(),
+            ins_pointer: self.ins_pointer,
+            relative_base: self.relative_base,
+            halted: self.halted,
+            stdin: self.stdin.clone(),
+            stdout: self.stdout.clone(),
+        }
+    }
+
+    /// Restores the state of the computer's execution from a snapshot. Breakpoints and
+    /// watchpoints are left as they are, and [`IntCodeComputer::reset`] still goes back to the
+    /// computer's original program.
+    ///
+    /// # Parameters
+    /// - `snapshot`: The snapshot, usually taken from a computer running the same program.
+    pub fn restore(&mut self, snapshot: &Snapshot<W>) {
+        self.memory = snapshot.memory.clone();
+        self.ins_pointer = snapshot.ins_pointer;
+        self.relative_base = snapshot.relative_base;
+        self.halted = snapshot.halted;
+        self.stdin = snapshot.stdin.clone();
+        self.stdout = snapshot.stdout.clone();
+        self.paused_at = None;
+        self.watchpoint_hit = None;
+    }
+
+    /// Saves the state of the computer's execution to a file.
+    ///
+    /// # Parameters
+    /// - `path`: The path to the file, which is overwritten if it exists.
+    ///
+    /// # Returns
+    /// An error if the file couldn't be written.
+    pub fn save_state<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.snapshot()
+            .write_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Restores the state of the computer's execution from a file written by
+    /// [`IntCodeComputer::save_state`].
+    ///
+    /// # Parameters
+    /// - `path`: The path to the file.
+    ///
+    /// # Returns
+    /// An error if the file couldn't be read or isn't a valid snapshot. In that case, the
+    /// computer isn't changed.
+    pub fn load_state<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let snapshot = Snapshot::read_from(BufReader::new(File::open(path)?))?;
+        self.restore(&snapshot);
+        Ok(())
+    }
+}
+
+/// Joins the values with commas, like an Intcode program.
+///
+/// # Parameters
+/// - `values`: The values.
+///
+/// # Returns
+/// The joined values.
+fn join<'a, W: Word>(values: impl IntoIterator<Item = &'a W>) -> String {
+    values
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses a single value of a snapshot.
+///
+/// # Parameters
+/// - `line_num`: The line number, for the error message.
+/// - `text`: The text to parse.
+///
+/// # Returns
+/// The value, or an error if it couldn't be parsed.
+fn parse<T: std::str::FromStr>(line_num: usize, text: &str) -> io::Result<T> {
+    text.trim()
+        .parse()
+        .map_err(|_| invalid(line_num, format!("invalid value \"{}\"", text.trim())))
+}
+
+/// Parses a comma-separated list of values of a snapshot.
+///
+/// # Parameters
+/// - `line_num`: The line number, for the error message.
+/// - `text`: The text to parse, which may be empty.
+///
+/// # Returns
+/// The values, or an error if any couldn't be parsed.
+fn parse_values<W: Word>(line_num: usize, text: &str) -> io::Result<Vec<W>> {
+    if text.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    text.split(',').map(|v| parse(line_num, v)).collect()
+}
+
+/// Creates the error for an invalid snapshot.
+///
+/// # Parameters
+/// - `line_num`: The line number that the problem is on, or `0` if it isn't on a line.
+/// - `message`: The problem.
+///
+/// # Returns
+/// The error.
+fn invalid(line_num: usize, message: impl Display) -> io::Error {
+    let message = if line_num == 0 {
+        format!("invalid snapshot: {message}")
+    } else {
+        format!("invalid snapshot: line {line_num}: {message}")
+    };
+
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::{assemble, parse_intcode, IntCodeComputer, RunState, Snapshot};
+
+    #[test]
+    pub fn test_clone() {
+        let program = parse_intcode("3,9,4,9,1105,1,0,99,0,0");
+        let mut c = IntCodeComputer::new(&program, Some(vec![1]));
+        assert_eq!(RunState::Output(1), c.run());
+
+        // Both computers carry on from the same point, but separately.
+        let mut clone = c.clone();
+        c.input_to_stdin(2);
+        clone.input_to_stdin(3);
+        assert_eq!(RunState::Output(2), c.run());
+        assert_eq!(RunState::Output(3), clone.run());
+        assert_eq!([1, 2].as_slice(), c.view_stdout());
+        assert_eq!([1, 3].as_slice(), clone.view_stdout());
+    }
+
+    #[test]
+    pub fn test_restore_io() {
+        let program = parse_intcode("3,9,4,9,1105,1,0,99,0,0");
+        let mut c = IntCodeComputer::new(&program, Some(vec![1, 2]));
+        assert_eq!(RunState::Output(1), c.run());
+        let snapshot = c.snapshot();
+
+        assert_eq!(RunState::Output(2), c.run());
+        assert_eq!(RunState::NeedsInput, c.run());
+        c.restore(&snapshot);
+        assert_eq!([1].as_slice(), c.view_stdout());
+        assert_eq!(RunState::Output(2), c.run());
+    }
+
+    #[test]
+    pub fn test_write_read() {
+        let program = assemble(
+            "
+                IN -> [2000]
+                OUT [2000]
+                IN -> [x]
+                HALT
+            x: DATA 0
+            ",
+        )
+        .unwrap();
+        let mut c = IntCodeComputer::new(&program, Some(vec![-5]));
+        assert_eq!(RunState::Output(-5), c.run());
+        c.input_to_stdin(9);
+        c.input_to_stdin(10);
+
+        let mut text = vec![];
+        c.snapshot().write_to(&mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert_eq!(
+            "intcode-snapshot 1\nip 4\nrb 0\nhalted false\nstdin 9,10\nstdout -5\nlen 2001\n\
+            mem 0 3,2000,4,2000,3,7,99\nmem 2000 -5\n",
+            text
+        );
+
+        let mut loaded = IntCodeComputer::new(&program, None);
+        loaded.restore(&Snapshot::read_from(text.as_bytes()).unwrap());
+        assert_eq!(c.view_program(), loaded.view_program());
+        assert_eq!(RunState::Halted, loaded.run());
+        assert_eq!(9, loaded[7]);
+        assert_eq!([10].as_slice(), loaded.stdin.make_contiguous());
+        assert_eq!([-5].as_slice(), loaded.view_stdout());
+    }
+
+    #[test]
+    pub fn test_save_load_file() {
+        let program = parse_intcode("3,9,4,9,1105,1,0,99,0,0");
+        let path = std::env::temp_dir().join(format!("intcode-{}.snapshot", std::process::id()));
+        let mut c = IntCodeComputer::new(&program, Some(vec![4]));
+        assert_eq!(RunState::Output(4), c.run());
+        c.save_state(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("\nstdin\nstdout 4\n"), "{text}");
+
+        let mut loaded = IntCodeComputer::new(&program, None);
+        loaded.load_state(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        loaded.input_to_stdin(6);
+        assert_eq!(RunState::Output(6), loaded.run());
+        assert_eq!([4, 6].as_slice(), loaded.view_stdout());
+        assert!(loaded.load_state(&path).is_err());
+    }
+
+    #[test]
+    pub fn test_read_invalid() {
+        for (text, message) in [
+            ("ip 0", "line 1: expected \"intcode-snapshot 1\""),
+            ("intcode-snapshot 1\nip x", "line 2: invalid value \"x\""),
+            (
+                "intcode-snapshot 1\nmem 0 1",
+                "line 2: \"mem\" before \"len\"",
+            ),
+            (
+                "intcode-snapshot 1\nlen 2\nmem 1 1,2",
+                "line 3: values past the end",
+            ),
+            ("intcode-snapshot 1\nfoo 1", "line 2: unknown key \"foo\""),
+            (
+                "intcode-snapshot 1\nip 0\nrb 0\nlen 0",
+                "missing \"halted\"",
+            ),
+        ] {
+            let err = Snapshot::<isize>::read_from(text.as_bytes()).err().unwrap();
+            assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+            assert!(err.to_string().contains(message), "{err}");
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// A signed integer type that an [`IntCodeComputer`](super::IntCodeComputer) can use for the
+/// values in its memory. Most programs fit in `isize` (the default), but `i128` can be used for
+/// programs whose arithmetic overflows 64 bits.
+pub trait Word: Copy + Eq + Ord + Hash + Debug + Display + FromStr + Send + Sync + 'static {
+    /// The value `0`.
+    const ZERO: Self;
+    /// The value `1`.
+    const ONE: Self;
+
+    /// Adds two values.
+    ///
+    /// # Parameters
+    /// - `rhs`: The value to add.
+    ///
+    /// # Returns
+    /// The sum, or `None` if it overflows.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Multiplies two values.
+    ///
+    /// # Parameters
+    /// - `rhs`: The value to multiply by.
+    ///
+    /// # Returns
+    /// The product, or `None` if it overflows.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Converts the value to an `isize`.
+    ///
+    /// # Returns
+    /// The value, or `None` if it doesn't fit in an `isize`.
+    fn to_isize(self) -> Option<isize>;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn to_isize(self) -> Option<isize> {
+                    isize::try_from(self).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(i32, i64, i128, isize);
@@ -0,0 +1,316 @@
+use super::IntInterval;
+
+/// An `N`-dimensional box of integer points, i.e., the points whose `i`th coordinate is in
+/// the `i`th interval for every axis `i`. The box is empty if any of its intervals is.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cuboid<const N: usize> {
+    axes: [IntInterval; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    /// Creates a new box.
+    ///
+    /// # Parameters
+    /// - `axes`: The interval of each axis.
+    ///
+    /// # Returns
+    /// The box.
+    pub fn new(axes: [IntInterval; N]) -> Self {
+        Self { axes }
+    }
+
+    /// Gets the intervals of this box.
+    ///
+    /// # Returns
+    /// The interval of each axis.
+    pub fn axes(&self) -> &[IntInterval; N] {
+        &self.axes
+    }
+
+    /// Checks if this box is empty.
+    ///
+    /// # Returns
+    /// `true` if no points are in this box, i.e., if the interval of any axis is empty.
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(|axis| axis.is_empty())
+    }
+
+    /// Gets the volume of this box.
+    ///
+    /// # Returns
+    /// The number of points in this box.
+    ///
+    /// # Panics
+    /// If the volume doesn't fit in a `usize`.
+    pub fn volume(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        self.axes
+            .iter()
+            .try_fold(1usize, |volume, axis| volume.checked_mul(axis.len()))
+            .expect("volume should fit in a usize")
+    }
+
+    /// Checks if the point is in this box.
+    ///
+    /// # Parameters
+    /// - `point`: The coordinates of the point.
+    ///
+    /// # Returns
+    /// `true` if the point is in this box, `false` otherwise.
+    pub fn contains(&self, point: [isize; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(axis, coord)| axis.contains(coord))
+    }
+
+    /// Intersects this box with another box.
+    ///
+    /// # Parameters
+    /// - `other`: The other box.
+    ///
+    /// # Returns
+    /// The box of points in both boxes, or `None` if they don't overlap.
+    pub fn intersect(&self, other: &Cuboid<N>) -> Option<Cuboid<N>> {
+        let mut axes = self.axes;
+        for (axis, other_axis) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersect(other_axis)?;
+        }
+
+        Some(Cuboid::new(axes))
+    }
+
+    /// Removes the points of another box from this box.
+    ///
+    /// # Parameters
+    /// - `other`: The box to remove.
+    ///
+    /// # Returns
+    /// Disjoint boxes covering the points in this box that aren't in `other`. There are at
+    /// most `2 * N` of them.
+    pub fn subtract(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        if self.is_empty() {
+            return vec![];
+        }
+
+        let Some(overlap) = self.intersect(other) else {
+            return vec![*self];
+        };
+
+        // Cut off the parts below and above the overlap one axis at a time, narrowing down
+        // what's left until only the overlap remains.
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for i in 0..N {
+            let (below, above) = rest.axes[i].difference(&overlap.axes[i]);
+            for part in [below, above].into_iter().flatten() {
+                let mut piece = rest;
+                piece.axes[i] = part;
+                pieces.push(piece);
+            }
+
+            rest.axes[i] = overlap.axes[i];
+        }
+
+        pieces
+    }
+}
+
+/// A set of integer points, stored as disjoint boxes. This is useful for turning large
+/// regions of a grid on and off, where storing each point would take too much memory.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CuboidSet<const N: usize> {
+    cuboids: Vec<Cuboid<N>>,
+}
+
+impl<const N: usize> CuboidSet<N> {
+    /// Creates an empty set.
+    ///
+    /// # Returns
+    /// The set.
+    pub fn new() -> Self {
+        Self { cuboids: vec![] }
+    }
+
+    /// Adds every point in the box to the set.
+    ///
+    /// # Parameters
+    /// - `cuboid`: The box. Empty boxes are ignored.
+    pub fn insert(&mut self, cuboid: Cuboid<N>) {
+        if cuboid.is_empty() {
+            return;
+        }
+
+        // Removing the box first keeps the boxes in the set disjoint.
+        self.remove(&cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    /// Removes every point in the box from the set, splitting the boxes in the set as needed.
+    ///
+    /// # Parameters
+    /// - `cuboid`: The box.
+    pub fn remove(&mut self, cuboid: &Cuboid<N>) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|c| c.subtract(cuboid))
+            .collect();
+    }
+
+    /// Checks if the point is in the set.
+    ///
+    /// # Parameters
+    /// - `point`: The coordinates of the point.
+    ///
+    /// # Returns
+    /// `true` if the point is in the set, `false` otherwise.
+    pub fn contains(&self, point: [isize; N]) -> bool {
+        self.cuboids.iter().any(|c| c.contains(point))
+    }
+
+    /// Gets the number of points in the set.
+    ///
+    /// # Returns
+    /// The number of points.
+    pub fn volume(&self) -> usize {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    /// Gets the number of points in the set that are also in the given box.
+    ///
+    /// # Parameters
+    /// - `bounds`: The box.
+    ///
+    /// # Returns
+    /// The number of points.
+    pub fn volume_within(&self, bounds: &Cuboid<N>) -> usize {
+        self.cuboids
+            .iter()
+            .filter_map(|c| c.intersect(bounds))
+            .map(|c| c.volume())
+            .sum()
+    }
+
+    /// Gets the number of disjoint boxes that make up the set.
+    ///
+    /// # Returns
+    /// The number of boxes.
+    pub fn num_cuboids(&self) -> usize {
+        self.cuboids.len()
+    }
+
+    /// Checks if the set is empty.
+    ///
+    /// # Returns
+    /// `true` if the set has no points.
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    /// Gets the boxes that make up the set.
+    ///
+    /// # Returns
+    /// An iterator over the disjoint boxes, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Cuboid<N>> + '_ {
+        self.cuboids.iter()
+    }
+}
+
+impl<const N: usize> FromIterator<Cuboid<N>> for CuboidSet<N> {
+    fn from_iter<I: IntoIterator<Item = Cuboid<N>>>(iter: I) -> Self {
+        let mut set = CuboidSet::new();
+        for cuboid in iter {
+            set.insert(cuboid);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod cuboid_tests {
+    use super::{Cuboid, CuboidSet};
+    use crate::interval::IntInterval;
+
+    /// Creates a 3-dimensional box.
+    fn cube(x: (isize, isize), y: (isize, isize), z: (isize, isize)) -> Cuboid<3> {
+        Cuboid::new([
+            IntInterval::new(x.0, x.1),
+            IntInterval::new(y.0, y.1),
+            IntInterval::new(z.0, z.1),
+        ])
+    }
+
+    #[test]
+    fn test_volume() {
+        assert_eq!(27, cube((10, 12), (10, 12), (10, 12)).volume());
+        assert_eq!(0, cube((10, 12), (10, 9), (10, 12)).volume());
+        assert_eq!(5, Cuboid::new([IntInterval::new(-2, 2)]).volume());
+        assert_eq!(
+            1 << 62,
+            Cuboid::new([IntInterval::new(-1 << 30, (1 << 30) - 1); 2]).volume()
+        );
+        assert_eq!(
+            usize::MAX,
+            Cuboid::new([IntInterval::new(isize::MIN, isize::MAX - 1)]).volume()
+        );
+        assert!(cube((0, 0), (0, 0), (0, 0)).contains([0, 0, 0]));
+        assert!(!cube((0, 0), (0, 0), (0, 0)).contains([0, 1, 0]));
+    }
+
+    #[test]
+    fn test_intersect() {
+        let c1 = cube((0, 10), (0, 10), (0, 10));
+        assert_eq!(
+            Some(cube((5, 10), (0, 3), (0, 10))),
+            c1.intersect(&cube((5, 20), (-5, 3), (-10, 10)))
+        );
+        assert_eq!(None, c1.intersect(&cube((5, 20), (11, 13), (0, 10))));
+    }
+
+    #[test]
+    fn test_subtract() {
+        let c1 = cube((0, 9), (0, 9), (0, 9));
+
+        // A hole in the middle leaves one piece on each side.
+        let pieces = c1.subtract(&cube((3, 5), (3, 5), (3, 5)));
+        assert_eq!(6, pieces.len());
+        assert_eq!(1000 - 27, pieces.iter().map(Cuboid::volume).sum::<usize>());
+        for (i, p1) in pieces.iter().enumerate() {
+            assert!(pieces[i + 1..].iter().all(|p2| p1.intersect(p2).is_none()));
+        }
+
+        assert_eq!(vec![c1], c1.subtract(&cube((20, 30), (0, 9), (0, 9))));
+        assert!(c1.subtract(&cube((-1, 10), (-1, 10), (-1, 10))).is_empty());
+        assert_eq!(
+            vec![cube((0, 4), (0, 9), (0, 9))],
+            c1.subtract(&cube((5, 100), (-5, 100), (-5, 100)))
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        // The small example from 2021 day 22.
+        let mut set = CuboidSet::new();
+        set.insert(cube((10, 12), (10, 12), (10, 12)));
+        assert_eq!(27, set.volume());
+        set.insert(cube((11, 13), (11, 13), (11, 13)));
+        assert_eq!(27 + 19, set.volume());
+        set.remove(&cube((9, 11), (9, 11), (9, 11)));
+        assert_eq!(27 + 19 - 8, set.volume());
+        set.insert(cube((10, 10), (10, 10), (10, 10)));
+        assert_eq!(39, set.volume());
+
+        assert!(set.contains([10, 10, 10]));
+        assert!(!set.contains([11, 11, 11]));
+        assert!(set.contains([13, 13, 13]));
+        assert_eq!(8, set.volume_within(&cube((12, 20), (12, 20), (12, 20))));
+
+        set.remove(&cube((0, 20), (0, 20), (0, 20)));
+        assert!(set.is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+use super::{IntInterval, IntervalSet};
+use std::collections::BTreeMap;
+
+/// A piecewise-linear map from integers to integers, made of rules that each shift a source
+/// interval by an offset. Integers that aren't covered by any rule map to themselves.
+///
+/// For example, the almanac entry `50 98 2` ("destination 50, source 98, length 2") is the rule
+/// `(IntInterval::new(98, 99), -48)`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RangeMap {
+    /// The rules, mapping the lower bound of each source interval to its upper bound and
+    /// offset. The source intervals are disjoint.
+    rules: BTreeMap<isize, (isize, isize)>,
+}
+
+impl RangeMap {
+    /// Creates a map with no rules, i.e., the identity map.
+    ///
+    /// # Returns
+    /// The map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule to the map. If the source overlaps the source of a rule that was already
+    /// added, the earlier rule takes precedence for the overlap.
+    ///
+    /// # Parameters
+    /// - `source`: The integers that the rule applies to.
+    /// - `offset`: The amount to add to each of those integers.
+    pub fn add_rule(&mut self, source: IntInterval, offset: isize) {
+        let covered = self
+            .pieces(source)
+            .into_iter()
+            .filter(|(_, offset)| offset.is_some())
+            .map(|(piece, _)| piece)
+            .collect::<IntervalSet>();
+        for piece in covered.complement(source).iter() {
+            self.rules.insert(piece.low, (piece.high, offset));
+        }
+    }
+
+    /// Maps a single value. This takes `O(log n)` time, where `n` is the number of rules.
+    ///
+    /// # Parameters
+    /// - `val`: The value.
+    ///
+    /// # Returns
+    /// The value, shifted by the offset of the rule that covers it (if any).
+    pub fn map_value(&self, val: isize) -> isize {
+        match self.rules.range(..=val).next_back() {
+            Some((_, &(high, offset))) if val <= high => val + offset,
+            _ => val,
+        }
+    }
+
+    /// Splits an interval at the boundaries of the rules' sources.
+    ///
+    /// # Parameters
+    /// - `interval`: The interval.
+    ///
+    /// # Returns
+    /// The pieces of the interval, in increasing order, each with the offset of the rule that
+    /// covers it, or `None` if no rule does.
+    pub fn pieces(&self, interval: IntInterval) -> Vec<(IntInterval, Option<isize>)> {
+        if interval.is_empty() {
+            return vec![];
+        }
+
+        // The first rule that could overlap the interval might start before it.
+        let start = self
+            .rules
+            .range(..interval.low)
+            .next_back()
+            .map_or(interval.low, |(&low, _)| low);
+
+        let mut pieces = vec![];
+        let mut next = interval.low;
+        for (&low, &(high, offset)) in self.rules.range(start..=interval.high) {
+            let Some(covered) = IntInterval::new(low, high).intersect(&interval) else {
+                continue;
+            };
+
+            if next < covered.low {
+                pieces.push((IntInterval::new(next, covered.low - 1), None));
+            }
+
+            pieces.push((covered, Some(offset)));
+            next = covered.high.saturating_add(1);
+        }
+
+        if next <= interval.high && pieces.last().is_none_or(|(p, _)| p.high < interval.high) {
+            pieces.push((IntInterval::new(next, interval.high), None));
+        }
+
+        pieces
+    }
+
+    /// Maps every integer in an interval.
+    ///
+    /// # Parameters
+    /// - `interval`: The interval.
+    ///
+    /// # Returns
+    /// The mapped pieces of the interval, in the order of the pieces they came from. Pieces
+    /// that no rule covers are passed through unchanged.
+    pub fn map_interval(&self, interval: IntInterval) -> Vec<IntInterval> {
+        self.pieces(interval)
+            .into_iter()
+            .map(|(piece, offset)| piece.shift(offset.unwrap_or(0)))
+            .collect()
+    }
+
+    /// Maps every integer in a set.
+    ///
+    /// # Parameters
+    /// - `set`: The set.
+    ///
+    /// # Returns
+    /// The set of mapped integers.
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.iter()
+            .flat_map(|interval| self.map_interval(interval))
+            .collect()
+    }
+
+    /// Composes this map with another map, so that applying the result is the same as
+    /// applying this map and then `next`.
+    ///
+    /// # Parameters
+    /// - `next`: The map to apply after this one.
+    ///
+    /// # Returns
+    /// The composed map.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut composed = RangeMap::new();
+        for (piece, offset) in self.pieces(IntInterval::new(isize::MIN, isize::MAX)) {
+            let offset = offset.unwrap_or(0);
+            for (image, next_offset) in next.pieces(piece.shift(offset)) {
+                let total = offset + next_offset.unwrap_or(0);
+                if total != 0 {
+                    composed
+                        .rules
+                        .insert(image.low - offset, (image.high - offset, total));
+                }
+            }
+        }
+
+        composed
+    }
+
+    /// Gets the rules of the map.
+    ///
+    /// # Returns
+    /// An iterator over the source interval and offset of each rule, in increasing order of
+    /// source. Overlapping rules have already been trimmed so that the sources are disjoint.
+    pub fn rules(&self) -> impl Iterator<Item = (IntInterval, isize)> + '_ {
+        self.rules
+            .iter()
+            .map(|(&low, &(high, offset))| (IntInterval::new(low, high), offset))
+    }
+}
+
+impl FromIterator<(IntInterval, isize)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (IntInterval, isize)>>(iter: I) -> Self {
+        let mut map = RangeMap::new();
+        for (source, offset) in iter {
+            map.add_rule(source, offset);
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod range_map_tests {
+    use super::RangeMap;
+    use crate::interval::{IntInterval, IntervalSet};
+
+    /// The seed-to-soil map from the example of 2023 day 5.
+    fn seed_to_soil() -> RangeMap {
+        [
+            (IntInterval::new(98, 99), -48),
+            (IntInterval::new(50, 97), 2),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    /// The soil-to-fertilizer map from the example of 2023 day 5.
+    fn soil_to_fertilizer() -> RangeMap {
+        [
+            (IntInterval::new(15, 51), -15),
+            (IntInterval::new(52, 53), -15),
+            (IntInterval::new(0, 14), 39),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_map_value() {
+        let map = seed_to_soil();
+        assert_eq!(81, map.map_value(79));
+        assert_eq!(14, map.map_value(14));
+        assert_eq!(57, map.map_value(55));
+        assert_eq!(50, map.map_value(98));
+        assert_eq!(100, map.map_value(100));
+    }
+
+    #[test]
+    fn test_pieces() {
+        let map = seed_to_soil();
+        assert_eq!(
+            vec![
+                (IntInterval::new(40, 49), None),
+                (IntInterval::new(50, 97), Some(2)),
+                (IntInterval::new(98, 99), Some(-48)),
+                (IntInterval::new(100, 110), None),
+            ],
+            map.pieces(IntInterval::new(40, 110))
+        );
+        assert_eq!(
+            vec![(IntInterval::new(60, 70), Some(2))],
+            map.pieces(IntInterval::new(60, 70))
+        );
+        assert_eq!(
+            vec![(IntInterval::new(0, 10), None)],
+            map.pieces(IntInterval::new(0, 10))
+        );
+        assert!(map.pieces(IntInterval::new(10, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_map_interval_and_set() {
+        let map = seed_to_soil();
+        assert_eq!(
+            vec![
+                IntInterval::new(45, 49),
+                IntInterval::new(52, 99),
+                IntInterval::new(50, 51),
+            ],
+            map.map_interval(IntInterval::new(45, 99))
+        );
+
+        let seeds = [IntInterval::new(79, 92), IntInterval::new(55, 67)]
+            .into_iter()
+            .collect::<IntervalSet>();
+        assert_eq!(
+            [IntInterval::new(57, 69), IntInterval::new(81, 94)]
+                .into_iter()
+                .collect::<IntervalSet>(),
+            map.map_set(&seeds)
+        );
+    }
+
+    #[test]
+    fn test_overlapping_rules() {
+        let map = [
+            (IntInterval::new(0, 10), 100),
+            (IntInterval::new(5, 20), 200),
+        ]
+        .into_iter()
+        .collect::<RangeMap>();
+        assert_eq!(
+            vec![
+                (IntInterval::new(0, 10), 100),
+                (IntInterval::new(11, 20), 200)
+            ],
+            map.rules().collect::<Vec<_>>()
+        );
+        assert_eq!(105, map.map_value(5));
+    }
+
+    #[test]
+    fn test_then() {
+        let first = seed_to_soil();
+        let second = soil_to_fertilizer();
+        let composed = first.then(&second);
+        for seed in -5..120 {
+            assert_eq!(
+                second.map_value(first.map_value(seed)),
+                composed.map_value(seed),
+                "seed {seed}"
+            );
+        }
+
+        // Composing with the identity changes nothing.
+        assert_eq!(first, first.then(&RangeMap::new()));
+        assert_eq!(first, RangeMap::new().then(&first));
+    }
+}
@@ -0,0 +1,530 @@
+use num_traits::PrimInt;
+use std::{
+    cmp::{max, min},
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+};
+
+mod cuboid;
+mod map;
+mod segment_tree;
+mod set;
+mod tree;
+pub use cuboid::{Cuboid, CuboidSet};
+pub use map::RangeMap;
+pub use segment_tree::SegmentTree;
+pub use set::IntervalSet;
+pub use tree::IntervalTree;
+
+/// Represents the interval `[low, high]`, where each element in the
+/// interval is an integer of type `T`. The interval is empty if `low > high`.
+///
+/// Intervals are ordered by their lower bound, then by their upper bound.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Interval<T> {
+    low: T,
+    high: T,
+}
+
+/// An interval of `isize`s.
+pub type IntInterval = Interval<isize>;
+
+impl<T: PrimInt> Interval<T> {
+    /// Creates a new interval interval representing `[low, high]`.
+    ///
+    /// # Parameters
+    /// - `low`: The lower-bound of the interval.
+    /// - `high`: The upper-bound of the interval.
+    ///
+    /// # Returns
+    /// The interval.
+    pub fn new(low: T, high: T) -> Interval<T> {
+        Interval { low, high }
+    }
+
+    /// Creates a new interval representing `[low, high]`. This is the same as
+    /// [`Interval::new`].
+    ///
+    /// # Parameters
+    /// - `low`: The lower-bound of the interval, inclusive.
+    /// - `high`: The upper-bound of the interval, inclusive.
+    ///
+    /// # Returns
+    /// The interval.
+    pub fn closed(low: T, high: T) -> Interval<T> {
+        Interval::new(low, high)
+    }
+
+    /// Creates a new interval representing `[low, high)`.
+    ///
+    /// # Parameters
+    /// - `low`: The lower-bound of the interval, inclusive.
+    /// - `high`: The upper-bound of the interval, exclusive.
+    ///
+    /// # Returns
+    /// The interval, which is empty if `high <= low`.
+    pub fn half_open(low: T, high: T) -> Interval<T> {
+        match high.checked_sub(&T::one()) {
+            Some(high) => Interval::new(low, high),
+            // Nothing is less than the smallest value.
+            None => Interval::new(T::max_value(), high),
+        }
+    }
+
+    /// Gets the lower-bound of this interval.
+    ///
+    /// # Returns
+    /// The lower-bound, inclusive.
+    pub fn low(&self) -> T {
+        self.low
+    }
+
+    /// Gets the upper-bound of this interval.
+    ///
+    /// # Returns
+    /// The upper-bound, inclusive.
+    pub fn high(&self) -> T {
+        self.high
+    }
+
+    /// Gets the length of this interval.
+    ///
+    /// # Returns
+    /// The length of this interval.
+    ///
+    /// # Panics
+    /// If the length doesn't fit in a `usize`.
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        // The difference doesn't always fit in `T` (e.g., `[-100, 100]` for `i8`), so find it
+        // as a `u128` instead. Only unsigned values can be too big for an `i128`.
+        let span = match (self.low.to_i128(), self.high.to_i128()) {
+            (Some(low), Some(high)) => high.abs_diff(low),
+            _ => self.high.to_u128().unwrap() - self.low.to_u128().unwrap(),
+        };
+        usize::try_from(span)
+            .ok()
+            .and_then(|len| len.checked_add(1))
+            .expect("interval length should fit in a usize")
+    }
+
+    /// Checks if this interval represents an empty interval.
+    ///
+    /// # Returns
+    /// `true` if no integers are in this interval. This only happens if `low > high`.
+    pub fn is_empty(&self) -> bool {
+        self.low > self.high
+    }
+
+    /// Checks if the value is in the interval.
+    ///
+    /// # Parameters
+    /// - `val`: The value to check.
+    ///
+    /// # Returns
+    /// `true` if the value is in the interval, `false` otherwise.
+    pub fn contains(&self, val: T) -> bool {
+        (self.low..=self.high).contains(&val)
+    }
+
+    /// Merges this interval with another interval. This is also known as the union of two intervals.
+    ///
+    /// # Parameters
+    /// - `other`: The other interval to merge.
+    ///
+    /// # Returns
+    /// The interval, if merging is possible. Otherwise, `None` is returned.
+    pub fn merge(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let ((left_min, left_max), (right_min, right_max)) = if self.low <= other.low {
+            ((self.low, self.high), (other.low, other.high))
+        } else {
+            ((other.low, other.high), (self.low, self.high))
+        };
+
+        if left_max.checked_add(&T::one()) == Some(right_min) || left_max >= right_min {
+            Some(Interval::new(
+                min(left_min, right_min),
+                max(left_max, right_max),
+            ))
+        } else {
+            // No overlap
+            None
+        }
+    }
+
+    /// Finds the commonly shared region between two intervals. This is also known as the intersection of two intervals.
+    ///
+    /// # Parameters
+    /// - `other`: The other interval to intersect with.
+    ///
+    /// # Returns
+    /// The interval, if intersection is possible. Otherwise, `None` is returned.
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let start_pt = max(self.low, other.low);
+        let end_pt = min(self.high, other.high);
+        if start_pt <= end_pt {
+            Some(Interval::new(start_pt, end_pt))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the integers in another interval from this interval. This is also known as the
+    /// difference of two intervals.
+    ///
+    /// # Parameters
+    /// - `other`: The interval to remove.
+    ///
+    /// # Returns
+    /// A tuple where the first element is the part of this interval below `other` and the second
+    /// element is the part above `other`, if those parts aren't empty.
+    pub fn difference(&self, other: &Interval<T>) -> (Option<Interval<T>>, Option<Interval<T>>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+
+        if other.is_empty() {
+            return (Some(*self), None);
+        }
+
+        let below = self.split_at(other.low).0;
+        let above = other
+            .high
+            .checked_add(&T::one())
+            .map(|after| self.split_at(after).1);
+        (
+            Some(below).filter(|i| !i.is_empty()),
+            above.filter(|i| !i.is_empty()),
+        )
+    }
+
+    /// Splits this interval at the given value.
+    ///
+    /// # Parameters
+    /// - `at`: The value to split at.
+    ///
+    /// # Returns
+    /// A tuple where the first element has the integers in this interval that are less than
+    /// `at` and the second element has the rest. Either one may be empty.
+    pub fn split_at(&self, at: T) -> (Interval<T>, Interval<T>) {
+        let below = Interval::half_open(self.low, min(at, self.high.saturating_add(T::one())));
+        let above = Interval::new(max(at, self.low), self.high);
+        (below, above)
+    }
+
+    /// Shifts this interval by the given amount.
+    ///
+    /// # Parameters
+    /// - `offset`: The amount to add to both bounds.
+    ///
+    /// # Returns
+    /// The shifted interval.
+    pub fn shift(&self, offset: T) -> Interval<T> {
+        Interval::new(self.low + offset, self.high + offset)
+    }
+
+    /// Converts this interval to a half-open range.
+    ///
+    /// # Returns
+    /// The range `low..high + 1`, or `None` if `high` is the largest value of `T` (so the range
+    /// can't be represented).
+    pub fn to_range(&self) -> Option<Range<T>> {
+        Some(self.low..self.high.checked_add(&T::one())?)
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (low, high) = range.into_inner();
+        Interval::new(low, high)
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Interval::half_open(range.start, range.end)
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.low..=interval.high
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("[{}, {}]", self.low, self.high))
+    }
+}
+
+#[cfg(test)]
+mod interval_tests {
+    use super::{IntInterval, Interval};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_len() {
+        let int1 = IntInterval::new(1, 5);
+        let int2 = IntInterval::new(250, 50);
+        let int3 = IntInterval::new(1, 1);
+
+        assert_eq!(5, int1.len());
+        assert_eq!(0, int2.len());
+        assert_eq!(1, int3.len());
+
+        // The length can be more than the largest value of the type.
+        assert_eq!(201, Interval::new(-100i8, 100).len());
+        assert_eq!(256, Interval::new(i8::MIN, i8::MAX).len());
+        assert_eq!((1usize << 63) + 1, IntInterval::new(-1, isize::MAX).len());
+        assert_eq!(
+            usize::MAX,
+            IntInterval::new(isize::MIN, isize::MAX - 1).len()
+        );
+        assert_eq!(11, Interval::new(u128::MAX - 10, u128::MAX).len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_len_too_long() {
+        Interval::new(0, u128::MAX).len();
+    }
+
+    #[test]
+    fn test_contains() {
+        let int1 = IntInterval::new(50, 250);
+        let int2 = IntInterval::new(250, 50);
+
+        assert!(int1.contains(50));
+        assert!(int1.contains(250));
+        assert!(int1.contains(66));
+        assert!(!int1.contains(49));
+        assert!(!int1.contains(251));
+
+        assert!(!int2.contains(50));
+        assert!(!int2.contains(250));
+        assert!(!int2.contains(66));
+        assert!(!int2.contains(49));
+        assert!(!int2.contains(251));
+    }
+
+    #[test]
+    fn test_merge_sanity() {
+        let int1 = IntInterval::new(10, 5);
+        let int2 = IntInterval::new(50, 15);
+        assert_eq!(None, int1.merge(&int2));
+        assert_eq!(None, int2.merge(&int1));
+    }
+
+    #[test]
+    fn test_merge_no_overlap() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(102, 150);
+
+        assert_eq!(None, int1.merge(&int2));
+        assert_eq!(None, int2.merge(&int1));
+    }
+
+    #[test]
+    fn test_merge_partial_enclosure() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(50, 150);
+
+        assert_eq!(Some(IntInterval::new(0, 150)), int1.merge(&int2));
+        assert_eq!(Some(IntInterval::new(0, 150)), int2.merge(&int1));
+    }
+
+    #[test]
+    fn test_merge_touching_interval() {
+        let int1 = IntInterval::new(0, 50);
+        let int2 = IntInterval::new(51, 100);
+        let int3 = IntInterval::new(50, 100);
+
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.merge(&int2));
+        assert_eq!(Some(IntInterval::new(0, 100)), int2.merge(&int1));
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.merge(&int3));
+        assert_eq!(Some(IntInterval::new(0, 100)), int3.merge(&int1));
+    }
+
+    #[test]
+    fn test_merge_enclose_general() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(10, 30);
+
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.merge(&int2));
+        assert_eq!(Some(IntInterval::new(0, 100)), int2.merge(&int1));
+    }
+
+    #[test]
+    fn test_merge_enclose_endpoint() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(0, 30);
+        let int4 = IntInterval::new(55, 100);
+
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.merge(&int2));
+        assert_eq!(Some(IntInterval::new(0, 100)), int2.merge(&int1));
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.merge(&int4));
+        assert_eq!(Some(IntInterval::new(0, 100)), int4.merge(&int1));
+    }
+
+    #[test]
+    fn test_merge_identity() {
+        let int1 = IntInterval::new(0, 100);
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.merge(&int1));
+    }
+
+    #[test]
+    fn test_intersect_identity() {
+        let int1 = IntInterval::new(0, 100);
+        assert_eq!(Some(IntInterval::new(0, 100)), int1.intersect(&int1));
+    }
+
+    #[test]
+    fn test_intersect_enclose() {
+        let int1 = IntInterval::new(10, 50);
+        let int2 = IntInterval::new(20, 40);
+
+        assert_eq!(Some(IntInterval::new(20, 40)), int1.intersect(&int2));
+        assert_eq!(Some(IntInterval::new(20, 40)), int2.intersect(&int1));
+    }
+
+    #[test]
+    fn test_intersect_enclose_endpoint() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(0, 30);
+        let int3 = IntInterval::new(70, 100);
+
+        assert_eq!(Some(IntInterval::new(0, 30)), int1.intersect(&int2));
+        assert_eq!(Some(IntInterval::new(0, 30)), int2.intersect(&int1));
+
+        assert_eq!(Some(IntInterval::new(70, 100)), int1.intersect(&int3));
+        assert_eq!(Some(IntInterval::new(70, 100)), int3.intersect(&int1));
+    }
+
+    #[test]
+    fn test_intersect_no_overlap() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(102, 150);
+
+        assert_eq!(None, int1.intersect(&int2));
+        assert_eq!(None, int2.intersect(&int1));
+    }
+
+    #[test]
+    fn test_intersect_partial_enclosure() {
+        let int1 = IntInterval::new(0, 100);
+        let int2 = IntInterval::new(50, 150);
+
+        assert_eq!(Some(IntInterval::new(50, 100)), int1.intersect(&int2));
+        assert_eq!(Some(IntInterval::new(50, 100)), int2.intersect(&int1));
+    }
+
+    #[test]
+    fn test_generic() {
+        let int1 = Interval::new(3u8, 250);
+        assert_eq!(248, int1.len());
+        assert!(int1.contains(3));
+        assert!(!int1.contains(251));
+        assert_eq!((3, 250), (int1.low(), int1.high()));
+
+        let int2 = Interval::new(i128::MAX - 1, i128::MAX);
+        assert_eq!(2, int2.len());
+        assert_eq!(
+            Some(Interval::new(i128::MAX - 5, i128::MAX)),
+            int2.merge(&Interval::new(i128::MAX - 5, i128::MAX - 2))
+        );
+    }
+
+    #[test]
+    fn test_half_open() {
+        assert_eq!(IntInterval::new(1, 4), IntInterval::half_open(1, 5));
+        assert!(IntInterval::half_open(5, 5).is_empty());
+        assert!(IntInterval::half_open(0, isize::MIN).is_empty());
+        assert!(Interval::half_open(0u32, 0).is_empty());
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(IntInterval::new(2, 7), IntInterval::from(2..=7));
+        assert_eq!(IntInterval::new(2, 6), IntInterval::from(2..7));
+        assert_eq!(2..=7, IntInterval::new(2, 7).into());
+        assert_eq!(Some(2..8), IntInterval::new(2, 7).to_range());
+        assert_eq!(None, Interval::new(1u8, 255).to_range());
+    }
+
+    #[test]
+    fn test_split_at() {
+        let int1 = IntInterval::new(10, 20);
+        assert_eq!(
+            (IntInterval::new(10, 14), IntInterval::new(15, 20)),
+            int1.split_at(15)
+        );
+
+        let (below, above) = int1.split_at(10);
+        assert!(below.is_empty());
+        assert_eq!(int1, above);
+
+        let (below, above) = int1.split_at(21);
+        assert_eq!(int1, below);
+        assert!(above.is_empty());
+
+        let (below, above) = int1.split_at(isize::MIN);
+        assert!(below.is_empty());
+        assert_eq!(int1, above);
+    }
+
+    #[test]
+    fn test_shift() {
+        assert_eq!(IntInterval::new(5, 15), IntInterval::new(10, 20).shift(-5));
+        assert_eq!(Interval::new(3u64, 4), Interval::new(1u64, 2).shift(2));
+    }
+
+    #[test]
+    fn test_difference() {
+        let int1 = IntInterval::new(0, 100);
+        assert_eq!(
+            (
+                Some(IntInterval::new(0, 9)),
+                Some(IntInterval::new(21, 100))
+            ),
+            int1.difference(&IntInterval::new(10, 20))
+        );
+        assert_eq!(
+            (None, Some(IntInterval::new(51, 100))),
+            int1.difference(&IntInterval::new(-10, 50))
+        );
+        assert_eq!(
+            (Some(IntInterval::new(0, 49)), None),
+            int1.difference(&IntInterval::new(50, isize::MAX))
+        );
+        assert_eq!((None, None), int1.difference(&IntInterval::new(0, 100)));
+        assert_eq!(
+            (Some(int1), None),
+            int1.difference(&IntInterval::new(200, 300))
+        );
+        assert_eq!((Some(int1), None), int1.difference(&IntInterval::new(5, 1)));
+    }
+
+    #[test]
+    fn test_ord_hash() {
+        let set = [
+            IntInterval::new(5, 10),
+            IntInterval::new(1, 20),
+            IntInterval::new(5, 6),
+            IntInterval::new(1, 20),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+        assert_eq!(
+            vec![
+                IntInterval::new(1, 20),
+                IntInterval::new(5, 6),
+                IntInterval::new(5, 10)
+            ],
+            set.into_iter().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use super::IntInterval;
+use std::cmp::{max, min};
+
+/// A change to every value in a range.
+#[derive(Copy, Clone, Debug)]
+enum Update {
+    /// Sets every value to the given value.
+    Assign(isize),
+    /// Adds the given amount to every value.
+    Add(isize),
+}
+
+/// The sum, minimum and maximum of a range of values.
+#[derive(Copy, Clone, Debug)]
+struct Summary {
+    sum: isize,
+    min: isize,
+    max: isize,
+}
+
+impl Summary {
+    /// Creates the summary of a range where every value is the same.
+    ///
+    /// # Parameters
+    /// - `value`: The value.
+    /// - `len`: The number of values in the range.
+    ///
+    /// # Returns
+    /// The summary.
+    fn uniform(value: isize, len: isize) -> Self {
+        Self {
+            sum: value * len,
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Combines the summaries of two adjacent ranges.
+    ///
+    /// # Parameters
+    /// - `other`: The summary of the other range.
+    ///
+    /// # Returns
+    /// The summary of both ranges.
+    fn combine(&self, other: &Summary) -> Self {
+        Self {
+            sum: self.sum + other.sum,
+            min: min(self.min, other.min),
+            max: max(self.max, other.max),
+        }
+    }
+}
+
+/// The changes that have been made to a node but not yet to its children. If both are set,
+/// the values are assigned first.
+#[derive(Copy, Clone, Debug, Default)]
+struct Pending {
+    assign: Option<isize>,
+    add: isize,
+}
+
+impl Pending {
+    /// Applies the pending changes to the summary of a range.
+    ///
+    /// # Parameters
+    /// - `summary`: The summary.
+    /// - `len`: The number of values in the range.
+    ///
+    /// # Returns
+    /// The summary after the changes.
+    fn apply(&self, summary: Summary, len: isize) -> Summary {
+        let summary = match self.assign {
+            Some(value) => Summary::uniform(value, len),
+            None => summary,
+        };
+
+        Summary {
+            sum: summary.sum + self.add * len,
+            min: summary.min + self.add,
+            max: summary.max + self.add,
+        }
+    }
+
+    /// Adds another change after these changes.
+    ///
+    /// # Parameters
+    /// - `update`: The change.
+    fn push(&mut self, update: Update) {
+        match update {
+            Update::Assign(value) => {
+                *self = Pending {
+                    assign: Some(value),
+                    add: 0,
+                }
+            }
+            Update::Add(delta) => self.add += delta,
+        }
+    }
+
+    /// Adds other pending changes after these changes.
+    ///
+    /// # Parameters
+    /// - `after`: The changes that were made after these ones.
+    ///
+    /// # Returns
+    /// The combined changes.
+    fn then(&self, after: &Pending) -> Pending {
+        match after.assign {
+            Some(_) => *after,
+            None => Pending {
+                assign: self.assign,
+                add: self.add + after.add,
+            },
+        }
+    }
+}
+
+/// A node of the segment tree.
+#[derive(Clone, Debug)]
+struct Node {
+    /// The summary of the node's range, including its pending changes.
+    summary: Summary,
+    /// The changes that haven't been made to the children yet.
+    pending: Pending,
+    /// The index of the left child, where the right child comes right after it. A node
+    /// without children has the same value everywhere in its range.
+    children: Option<usize>,
+}
+
+/// A segment tree over the integers in some bounds, supporting setting or adding to every
+/// value in a range and getting the sum, minimum or maximum of a range, all in `O(log n)`
+/// time, where `n` is the length of the bounds.
+///
+/// Changes are made lazily, and nodes are only created once a change splits them. This means
+/// that the bounds can be large (e.g., millions of positions) while only using memory for the
+/// ranges that were actually changed. Every value starts at `0`.
+#[derive(Clone, Debug)]
+pub struct SegmentTree {
+    bounds: IntInterval,
+    nodes: Vec<Node>,
+}
+
+impl SegmentTree {
+    /// Creates a segment tree where every value is `0`.
+    ///
+    /// # Parameters
+    /// - `bounds`: The positions that the tree has values for.
+    ///
+    /// # Returns
+    /// The segment tree.
+    ///
+    /// # Panics
+    /// If the bounds are empty.
+    pub fn new(bounds: IntInterval) -> Self {
+        assert!(!bounds.is_empty(), "bounds should not be empty");
+        Self {
+            bounds,
+            nodes: vec![Node {
+                summary: Summary {
+                    sum: 0,
+                    min: 0,
+                    max: 0,
+                },
+                pending: Pending::default(),
+                children: None,
+            }],
+        }
+    }
+
+    /// Gets the bounds of this tree.
+    ///
+    /// # Returns
+    /// The positions that the tree has values for.
+    pub fn bounds(&self) -> IntInterval {
+        self.bounds
+    }
+
+    /// Sets every value in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range. Nothing happens if it's empty.
+    /// - `value`: The new value.
+    ///
+    /// # Panics
+    /// If the range isn't within the bounds.
+    pub fn assign(&mut self, range: IntInterval, value: isize) {
+        self.update(range, Update::Assign(value));
+    }
+
+    /// Adds an amount to every value in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range. Nothing happens if it's empty.
+    /// - `delta`: The amount to add.
+    ///
+    /// # Panics
+    /// If the range isn't within the bounds.
+    pub fn add(&mut self, range: IntInterval, delta: isize) {
+        self.update(range, Update::Add(delta));
+    }
+
+    /// Gets the value at a position.
+    ///
+    /// # Parameters
+    /// - `pos`: The position.
+    ///
+    /// # Returns
+    /// The value.
+    ///
+    /// # Panics
+    /// If the position isn't within the bounds.
+    pub fn get(&self, pos: isize) -> isize {
+        self.sum(IntInterval::new(pos, pos))
+    }
+
+    /// Gets the sum of the values in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    ///
+    /// # Returns
+    /// The sum, which is `0` if the range is empty.
+    ///
+    /// # Panics
+    /// If the range isn't within the bounds.
+    pub fn sum(&self, range: IntInterval) -> isize {
+        self.summarize(range).map_or(0, |summary| summary.sum)
+    }
+
+    /// Gets the smallest value in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    ///
+    /// # Returns
+    /// The smallest value, or `None` if the range is empty.
+    ///
+    /// # Panics
+    /// If the range isn't within the bounds.
+    pub fn min(&self, range: IntInterval) -> Option<isize> {
+        self.summarize(range).map(|summary| summary.min)
+    }
+
+    /// Gets the largest value in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    ///
+    /// # Returns
+    /// The largest value, or `None` if the range is empty.
+    ///
+    /// # Panics
+    /// If the range isn't within the bounds.
+    pub fn max(&self, range: IntInterval) -> Option<isize> {
+        self.summarize(range).map(|summary| summary.max)
+    }
+
+    /// Finds the first position in a range whose value is at most some value. For example,
+    /// if the values count how many times each position is covered, this finds the first
+    /// position that isn't covered.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    /// - `value`: The value.
+    ///
+    /// # Returns
+    /// The position, or `None` if every value in the range is larger.
+    ///
+    /// # Panics
+    /// If the range isn't within the bounds.
+    pub fn first_at_most(&self, range: IntInterval, value: isize) -> Option<isize> {
+        if range.is_empty() {
+            return None;
+        }
+
+        self.check_bounds(range);
+        self.find_first(0, self.bounds, Pending::default(), range, value)
+    }
+
+    /// Panics if a non-empty range isn't within the bounds.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    fn check_bounds(&self, range: IntInterval) {
+        assert!(
+            self.bounds.low() <= range.low() && range.high() <= self.bounds.high(),
+            "range {range} should be within the bounds {}",
+            self.bounds
+        );
+    }
+
+    /// Changes every value in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    /// - `update`: The change.
+    fn update(&mut self, range: IntInterval, update: Update) {
+        if range.is_empty() {
+            return;
+        }
+
+        self.check_bounds(range);
+        self.update_node(0, self.bounds, range, update);
+    }
+
+    /// Changes every value in the part of a range that's in a node's range.
+    ///
+    /// # Parameters
+    /// - `idx`: The index of the node.
+    /// - `span`: The node's range.
+    /// - `range`: The range to change.
+    /// - `update`: The change.
+    fn update_node(&mut self, idx: usize, span: IntInterval, range: IntInterval, update: Update) {
+        if range.high() < span.low() || span.high() < range.low() {
+            return;
+        }
+
+        if range.low() <= span.low() && span.high() <= range.high() {
+            self.apply(idx, span, update);
+            return;
+        }
+
+        let (left_span, right_span) = split(span);
+        let left = self.push_down(idx, span);
+        self.update_node(left, left_span, range, update);
+        self.update_node(left + 1, right_span, range, update);
+        self.nodes[idx].summary = self.nodes[left]
+            .summary
+            .combine(&self.nodes[left + 1].summary);
+    }
+
+    /// Changes every value in a node's range.
+    ///
+    /// # Parameters
+    /// - `idx`: The index of the node.
+    /// - `span`: The node's range.
+    /// - `update`: The change.
+    fn apply(&mut self, idx: usize, span: IntInterval, update: Update) {
+        let node = &mut self.nodes[idx];
+        let mut pending = Pending::default();
+        pending.push(update);
+        node.summary = pending.apply(node.summary, span_len(span));
+        if node.children.is_some() {
+            node.pending.push(update);
+        }
+    }
+
+    /// Makes a node's pending changes to its children, creating the children if it doesn't
+    /// have any.
+    ///
+    /// # Parameters
+    /// - `idx`: The index of the node.
+    /// - `span`: The node's range.
+    ///
+    /// # Returns
+    /// The index of the left child.
+    fn push_down(&mut self, idx: usize, span: IntInterval) -> usize {
+        let (left_span, right_span) = split(span);
+        let node = &self.nodes[idx];
+        let Some(left) = node.children else {
+            // A node without children has the same value everywhere.
+            let value = node.summary.min;
+            let left = self.nodes.len();
+            for child_span in [left_span, right_span] {
+                self.nodes.push(Node {
+                    summary: Summary::uniform(value, span_len(child_span)),
+                    pending: Pending::default(),
+                    children: None,
+                });
+            }
+
+            self.nodes[idx].children = Some(left);
+            return left;
+        };
+
+        let pending = node.pending;
+        for (child, child_span) in [(left, left_span), (left + 1, right_span)] {
+            let child = &mut self.nodes[child];
+            child.summary = pending.apply(child.summary, span_len(child_span));
+            if child.children.is_some() {
+                child.pending = child.pending.then(&pending);
+            }
+        }
+
+        self.nodes[idx].pending = Pending::default();
+        left
+    }
+
+    /// Summarizes the values in a range.
+    ///
+    /// # Parameters
+    /// - `range`: The range.
+    ///
+    /// # Returns
+    /// The summary, or `None` if the range is empty.
+    fn summarize(&self, range: IntInterval) -> Option<Summary> {
+        if range.is_empty() {
+            return None;
+        }
+
+        self.check_bounds(range);
+        self.summarize_node(0, self.bounds, Pending::default(), range)
+    }
+
+    /// Summarizes the values in the part of a range that's in a node's range.
+    ///
+    /// # Parameters
+    /// - `idx`: The index of the node.
+    /// - `span`: The node's range.
+    /// - `above`: The changes that the node's ancestors haven't made to it yet.
+    /// - `range`: The range to summarize.
+    ///
+    /// # Returns
+    /// The summary, or `None` if the ranges don't overlap.
+    fn summarize_node(
+        &self,
+        idx: usize,
+        span: IntInterval,
+        above: Pending,
+        range: IntInterval,
+    ) -> Option<Summary> {
+        let overlap = span.intersect(&range)?;
+        let node = &self.nodes[idx];
+        let Some(left) = node.children else {
+            let value = above.apply(node.summary, span_len(span)).min;
+            return Some(Summary::uniform(value, span_len(overlap)));
+        };
+
+        if overlap == span {
+            return Some(above.apply(node.summary, span_len(span)));
+        }
+
+        let above = node.pending.then(&above);
+        let (left_span, right_span) = split(span);
+        match (
+            self.summarize_node(left, left_span, above, range),
+            self.summarize_node(left + 1, right_span, above, range),
+        ) {
+            (Some(l), Some(r)) => Some(l.combine(&r)),
+            (l, r) => l.or(r),
+        }
+    }
+
+    /// Finds the first position in the part of a range that's in a node's range whose value
+    /// is at most some value.
+    ///
+    /// # Parameters
+    /// - `idx`: The index of the node.
+    /// - `span`: The node's range.
+    /// - `above`: The changes that the node's ancestors haven't made to it yet.
+    /// - `range`: The range to search.
+    /// - `value`: The value.
+    ///
+    /// # Returns
+    /// The position, if any.
+    fn find_first(
+        &self,
+        idx: usize,
+        span: IntInterval,
+        above: Pending,
+        range: IntInterval,
+        value: isize,
+    ) -> Option<isize> {
+        let overlap = span.intersect(&range)?;
+        let node = &self.nodes[idx];
+        if above.apply(node.summary, span_len(span)).min > value {
+            return None;
+        }
+
+        let Some(left) = node.children else {
+            return Some(overlap.low());
+        };
+
+        let above = node.pending.then(&above);
+        let (left_span, right_span) = split(span);
+        self.find_first(left, left_span, above, range, value)
+            .or_else(|| self.find_first(left + 1, right_span, above, range, value))
+    }
+}
+
+/// Splits a range (of at least two integers) into two halves.
+///
+/// # Parameters
+/// - `span`: The range.
+///
+/// # Returns
+/// The left and right halves.
+fn split(span: IntInterval) -> (IntInterval, IntInterval) {
+    let (low, high) = (span.low(), span.high());
+    // The average, rounded down, without overflowing.
+    let mid = (low & high) + ((low ^ high) >> 1);
+    (IntInterval::new(low, mid), IntInterval::new(mid + 1, high))
+}
+
+/// Gets the number of integers in a range.
+///
+/// # Parameters
+/// - `span`: The range.
+///
+/// # Returns
+/// The number of integers.
+fn span_len(span: IntInterval) -> isize {
+    span.high() - span.low() + 1
+}
+
+#[cfg(test)]
+mod segment_tree_tests {
+    use super::SegmentTree;
+    use crate::interval::IntInterval;
+
+    #[test]
+    fn test_add_and_assign() {
+        let mut tree = SegmentTree::new(IntInterval::new(-10, 10));
+        tree.add(IntInterval::new(-5, 5), 2);
+        tree.add(IntInterval::new(0, 10), 3);
+        assert_eq!(2, tree.get(-1));
+        assert_eq!(5, tree.get(0));
+        assert_eq!(3, tree.get(10));
+        assert_eq!(2 * 11 + 3 * 11, tree.sum(IntInterval::new(-10, 10)));
+        assert_eq!(Some(0), tree.min(IntInterval::new(-10, 10)));
+        assert_eq!(Some(5), tree.max(IntInterval::new(-10, 10)));
+
+        tree.assign(IntInterval::new(-2, 2), -1);
+        tree.add(IntInterval::new(2, 3), 1);
+        assert_eq!(
+            vec![2, -1, -1, -1, -1, 0, 6],
+            (-3..=3).map(|pos| tree.get(pos)).collect::<Vec<_>>()
+        );
+        assert_eq!(None, tree.min(IntInterval::new(1, 0)));
+        assert_eq!(0, tree.sum(IntInterval::new(1, 0)));
+    }
+
+    #[test]
+    fn test_first_at_most() {
+        let mut tree = SegmentTree::new(IntInterval::new(0, 4_000_000));
+        tree.add(IntInterval::new(0, 1_234_566), 1);
+        tree.add(IntInterval::new(1_234_568, 4_000_000), 1);
+        tree.add(IntInterval::new(2_000_000, 3_000_000), 1);
+        assert_eq!(
+            Some(1_234_567),
+            tree.first_at_most(IntInterval::new(0, 4_000_000), 0)
+        );
+        assert_eq!(
+            None,
+            tree.first_at_most(IntInterval::new(1_234_568, 4_000_000), 0)
+        );
+        assert_eq!(
+            Some(0),
+            tree.first_at_most(IntInterval::new(0, 4_000_000), 1)
+        );
+        assert_eq!(
+            Some(1_234_568),
+            tree.first_at_most(IntInterval::new(1_234_568, 4_000_000), 1)
+        );
+    }
+
+    #[test]
+    fn test_against_array() {
+        let bounds = IntInterval::new(-20, 43);
+        let mut tree = SegmentTree::new(bounds);
+        let mut values = vec![0isize; bounds.len()];
+        let mut seed = 12345usize;
+        let mut next = |n: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+
+        for _ in 0..500 {
+            let a = next(values.len());
+            let b = next(values.len());
+            let (a, b) = (a.min(b), a.max(b));
+            let range = IntInterval::new(a as isize - 20, b as isize - 20);
+            let value = next(21) as isize - 10;
+            match next(3) {
+                0 => {
+                    tree.assign(range, value);
+                    values[a..=b].fill(value);
+                }
+                1 => {
+                    tree.add(range, value);
+                    values[a..=b].iter_mut().for_each(|v| *v += value);
+                }
+                _ => {
+                    let slice = &values[a..=b];
+                    assert_eq!(slice.iter().sum::<isize>(), tree.sum(range));
+                    assert_eq!(slice.iter().min().copied(), tree.min(range));
+                    assert_eq!(slice.iter().max().copied(), tree.max(range));
+                    assert_eq!(
+                        slice
+                            .iter()
+                            .position(|v| *v <= 0)
+                            .map(|i| (a + i) as isize - 20),
+                        tree.first_at_most(range, 0)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_bounds() {
+        SegmentTree::new(IntInterval::new(0, 10)).add(IntInterval::new(5, 11), 1);
+    }
+}
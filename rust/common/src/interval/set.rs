@@ -0,0 +1,389 @@
+use super::IntInterval;
+use std::cmp::max;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// A set of integers, stored as sorted, disjoint intervals. Intervals that overlap or touch
+/// are always coalesced, so two sets with the same integers are equal.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct IntervalSet {
+    /// The intervals, mapping the lower bound of each interval to its upper bound.
+    ranges: BTreeMap<isize, isize>,
+}
+
+impl IntervalSet {
+    /// Creates an empty set.
+    ///
+    /// # Returns
+    /// The set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every integer in the interval to the set.
+    ///
+    /// # Parameters
+    /// - `interval`: The interval. Empty intervals are ignored.
+    pub fn insert(&mut self, interval: IntInterval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let IntInterval { mut low, mut high } = interval;
+        // An interval that starts before this one might overlap or touch it.
+        if let Some((&l, &h)) = self.ranges.range(..low).next_back() {
+            if h >= low - 1 {
+                low = l;
+                high = max(high, h);
+            }
+        }
+
+        // So might any interval that starts within (or right after) this one.
+        let absorbed = self
+            .ranges
+            .range(low..=high.saturating_add(1))
+            .map(|(&l, _)| l)
+            .collect::<Vec<_>>();
+        for l in absorbed {
+            high = max(high, self.ranges.remove(&l).unwrap());
+        }
+
+        self.ranges.insert(low, high);
+    }
+
+    /// Removes every integer in the interval from the set, splitting the intervals in the set
+    /// as needed.
+    ///
+    /// # Parameters
+    /// - `interval`: The interval. Empty intervals are ignored.
+    pub fn remove(&mut self, interval: IntInterval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let IntInterval { low, high } = interval;
+        if let Some((&l, &h)) = self.ranges.range(..low).next_back() {
+            if h >= low {
+                self.ranges.insert(l, low - 1);
+                if h > high {
+                    self.ranges.insert(high + 1, h);
+                }
+            }
+        }
+
+        let overlapping = self
+            .ranges
+            .range(low..=high)
+            .map(|(&l, &h)| (l, h))
+            .collect::<Vec<_>>();
+        for (l, h) in overlapping {
+            self.ranges.remove(&l);
+            if h > high {
+                self.ranges.insert(high + 1, h);
+            }
+        }
+    }
+
+    /// Checks if the value is in the set. This takes `O(log n)` time, where `n` is the number
+    /// of intervals in the set.
+    ///
+    /// # Parameters
+    /// - `val`: The value to check.
+    ///
+    /// # Returns
+    /// `true` if the value is in the set, `false` otherwise.
+    pub fn contains(&self, val: isize) -> bool {
+        self.interval_containing(val).is_some()
+    }
+
+    /// Finds the interval in the set that contains the value. This takes `O(log n)` time,
+    /// where `n` is the number of intervals in the set.
+    ///
+    /// # Parameters
+    /// - `val`: The value.
+    ///
+    /// # Returns
+    /// The interval, or `None` if the value isn't in the set.
+    pub fn interval_containing(&self, val: isize) -> Option<IntInterval> {
+        self.ranges
+            .range(..=val)
+            .next_back()
+            .filter(|(_, &h)| h >= val)
+            .map(|(&l, &h)| IntInterval::new(l, h))
+    }
+
+    /// Gets the union of this set and another set.
+    ///
+    /// # Parameters
+    /// - `other`: The other set.
+    ///
+    /// # Returns
+    /// A set with the integers that are in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        other.iter().for_each(|interval| union.insert(interval));
+        union
+    }
+
+    /// Gets the intersection of this set and another set.
+    ///
+    /// # Parameters
+    /// - `other`: The other set.
+    ///
+    /// # Returns
+    /// A set with the integers that are in both sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intersection = IntervalSet::new();
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            if let Some(common) = l.intersect(r) {
+                intersection.ranges.insert(common.low, common.high);
+            }
+
+            // Whichever interval ends first can't overlap anything else in the other set.
+            if l.high < r.high {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        intersection
+    }
+
+    /// Gets the difference of this set and another set.
+    ///
+    /// # Parameters
+    /// - `other`: The other set.
+    ///
+    /// # Returns
+    /// A set with the integers that are in this set but not the other set.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        other
+            .iter()
+            .for_each(|interval| difference.remove(interval));
+        difference
+    }
+
+    /// Gets the complement of this set within the given bounds.
+    ///
+    /// # Parameters
+    /// - `bounds`: The bounds.
+    ///
+    /// # Returns
+    /// A set with the integers in `bounds` that aren't in this set.
+    pub fn complement(&self, bounds: IntInterval) -> IntervalSet {
+        let mut complement = IntervalSet::new();
+        complement.insert(bounds);
+        complement.difference(self)
+    }
+
+    /// Gets the number of integers in the set.
+    ///
+    /// # Returns
+    /// The sum of the lengths of the intervals in the set.
+    pub fn total_len(&self) -> usize {
+        self.iter().map(|interval| interval.len()).sum()
+    }
+
+    /// Gets the number of intervals that the set is made up of.
+    ///
+    /// # Returns
+    /// The number of intervals.
+    pub fn num_intervals(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Checks if the set is empty.
+    ///
+    /// # Returns
+    /// `true` if there are no integers in the set.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Gets the intervals that make up the set.
+    ///
+    /// # Returns
+    /// An iterator over the intervals, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = IntInterval> + '_ {
+        self.ranges.iter().map(|(&l, &h)| IntInterval::new(l, h))
+    }
+
+    /// Gets the gaps between the intervals that make up the set.
+    ///
+    /// # Returns
+    /// An iterator over the intervals of integers that aren't in the set, but are between the
+    /// lowest and highest integers that are, in increasing order.
+    pub fn gaps(&self) -> impl Iterator<Item = IntInterval> + '_ {
+        self.ranges
+            .iter()
+            .zip(self.ranges.iter().skip(1))
+            .map(|((_, &h), (&l, _))| IntInterval::new(h + 1, l - 1))
+    }
+
+    /// Gets the smallest integer in the set.
+    ///
+    /// # Returns
+    /// The smallest integer, or `None` if the set is empty.
+    pub fn min(&self) -> Option<isize> {
+        self.ranges.first_key_value().map(|(&low, _)| low)
+    }
+
+    /// Gets the largest integer in the set.
+    ///
+    /// # Returns
+    /// The largest integer, or `None` if the set is empty.
+    pub fn max(&self) -> Option<isize> {
+        self.ranges.last_key_value().map(|(_, &high)| high)
+    }
+
+    /// Gets the smallest interval that contains every integer in the set.
+    ///
+    /// # Returns
+    /// The interval, or `None` if the set is empty.
+    pub fn span(&self) -> Option<IntInterval> {
+        let (&low, _) = self.ranges.first_key_value()?;
+        let (_, &high) = self.ranges.last_key_value()?;
+        Some(IntInterval::new(low, high))
+    }
+}
+
+impl FromIterator<IntInterval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = IntInterval>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|interval| set.insert(interval));
+        set
+    }
+}
+
+impl Extend<IntInterval> for IntervalSet {
+    fn extend<I: IntoIterator<Item = IntInterval>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|interval| self.insert(interval));
+    }
+}
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("{")?;
+        for (i, interval) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{interval}")?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+#[cfg(test)]
+mod interval_set_tests {
+    use super::IntervalSet;
+    use crate::interval::IntInterval;
+
+    /// Creates a set from `(low, high)` pairs.
+    fn set(intervals: &[(isize, isize)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(low, high)| IntInterval::new(low, high))
+            .collect()
+    }
+
+    /// Gets the `(low, high)` pairs of a set.
+    fn pairs(set: &IntervalSet) -> Vec<(isize, isize)> {
+        set.ranges.iter().map(|(&l, &h)| (l, h)).collect()
+    }
+
+    #[test]
+    fn test_insert_coalesces() {
+        let mut s = set(&[(10, 20), (30, 40), (50, 60)]);
+        assert_eq!(vec![(10, 20), (30, 40), (50, 60)], pairs(&s));
+
+        // Touching intervals are merged.
+        s.insert(IntInterval::new(21, 25));
+        assert_eq!(vec![(10, 25), (30, 40), (50, 60)], pairs(&s));
+
+        // An interval can swallow several others.
+        s.insert(IntInterval::new(26, 55));
+        assert_eq!(vec![(10, 60)], pairs(&s));
+
+        s.insert(IntInterval::new(15, 16));
+        s.insert(IntInterval::new(5, 1));
+        assert_eq!(vec![(10, 60)], pairs(&s));
+        assert_eq!(51, s.total_len());
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut s = set(&[(0, 100)]);
+        s.remove(IntInterval::new(10, 19));
+        assert_eq!(vec![(0, 9), (20, 100)], pairs(&s));
+
+        s.remove(IntInterval::new(5, 50));
+        assert_eq!(vec![(0, 4), (51, 100)], pairs(&s));
+
+        s.remove(IntInterval::new(-10, 0));
+        s.remove(IntInterval::new(100, 200));
+        assert_eq!(vec![(1, 4), (51, 99)], pairs(&s));
+
+        s.remove(IntInterval::new(-1000, 1000));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let s = set(&[(-5, 5), (10, 10)]);
+        assert!(s.contains(-5));
+        assert!(s.contains(5));
+        assert!(s.contains(10));
+        assert!(!s.contains(6));
+        assert!(!s.contains(11));
+        assert!(!s.contains(-6));
+        assert_eq!(Some(IntInterval::new(-5, 5)), s.interval_containing(0));
+        assert_eq!(None, s.interval_containing(9));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 45)]);
+        assert_eq!(vec![(0, 30), (40, 45)], pairs(&a.union(&b)));
+        assert_eq!(vec![(5, 10), (20, 25)], pairs(&a.intersection(&b)));
+        assert_eq!(vec![(0, 4), (26, 30)], pairs(&a.difference(&b)));
+        assert_eq!(vec![(11, 19), (40, 45)], pairs(&b.difference(&a)));
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_complement_and_gaps() {
+        let s = set(&[(0, 10), (20, 30), (35, 35)]);
+        assert_eq!(
+            vec![(-5, -1), (11, 19), (31, 34), (36, 40)],
+            pairs(&s.complement(IntInterval::new(-5, 40)))
+        );
+        assert_eq!(
+            vec![IntInterval::new(11, 19), IntInterval::new(31, 34)],
+            s.gaps().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(IntInterval::new(0, 35)), s.span());
+        assert_eq!((Some(0), Some(35)), (s.min(), s.max()));
+        assert_eq!(None, IntervalSet::new().min());
+        assert_eq!(3, s.num_intervals());
+        assert_eq!("{[0, 10], [20, 30], [35, 35]}", s.to_string());
+    }
+
+    #[test]
+    fn test_extreme_bounds() {
+        let mut s = set(&[(isize::MIN, -1), (1, isize::MAX)]);
+        assert_eq!(2, s.num_intervals());
+        s.insert(IntInterval::new(0, 0));
+        assert_eq!(vec![(isize::MIN, isize::MAX)], pairs(&s));
+        s.remove(IntInterval::new(isize::MAX, isize::MAX));
+        assert_eq!(vec![(isize::MIN, isize::MAX - 1)], pairs(&s));
+        assert_eq!(usize::MAX, s.total_len());
+    }
+}
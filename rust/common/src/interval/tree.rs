@@ -0,0 +1,241 @@
+use super::IntInterval;
+use std::cmp::max;
+
+/// A static collection of (possibly overlapping) intervals, each with a value, that can
+/// quickly find the intervals containing a point or overlapping another interval.
+///
+/// The intervals are kept sorted in an implicit balanced binary search tree, where each node
+/// also knows the largest upper bound in its subtree. This lets queries skip subtrees that
+/// can't overlap. A query takes `O(log n + k)` time, where `k` is the number of results.
+#[derive(Clone, Debug)]
+pub struct IntervalTree<V> {
+    /// The intervals and their values, sorted by interval.
+    entries: Vec<(IntInterval, V)>,
+    /// The largest upper bound in the subtree rooted at each entry.
+    max_high: Vec<isize>,
+}
+
+impl<V> IntervalTree<V> {
+    /// Creates a tree holding the given intervals.
+    ///
+    /// # Parameters
+    /// - `entries`: The intervals and their values. Empty intervals are left out.
+    ///
+    /// # Returns
+    /// The tree.
+    pub fn new(mut entries: Vec<(IntInterval, V)>) -> Self {
+        entries.retain(|(interval, _)| !interval.is_empty());
+        entries.sort_by_key(|(interval, _)| *interval);
+
+        let mut tree = Self {
+            max_high: vec![isize::MIN; entries.len()],
+            entries,
+        };
+        tree.build(0, tree.entries.len());
+        tree
+    }
+
+    /// Gets the intervals that contain a point, i.e., a stabbing query.
+    ///
+    /// # Parameters
+    /// - `point`: The point.
+    ///
+    /// # Returns
+    /// The intervals containing the point and their values, sorted by interval.
+    pub fn stab(&self, point: isize) -> Vec<(IntInterval, &V)> {
+        self.overlapping(IntInterval::new(point, point))
+    }
+
+    /// Gets the intervals that overlap an interval.
+    ///
+    /// # Parameters
+    /// - `interval`: The interval.
+    ///
+    /// # Returns
+    /// The intervals sharing at least one integer with `interval` and their values, sorted
+    /// by interval.
+    pub fn overlapping(&self, interval: IntInterval) -> Vec<(IntInterval, &V)> {
+        let mut found = vec![];
+        if !interval.is_empty() {
+            self.search(0, self.entries.len(), interval, &mut found);
+        }
+
+        found
+    }
+
+    /// Gets the number of intervals in the tree.
+    ///
+    /// # Returns
+    /// The number of intervals.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if the tree is empty.
+    ///
+    /// # Returns
+    /// `true` if the tree has no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the intervals in the tree.
+    ///
+    /// # Returns
+    /// An iterator over the intervals and their values, sorted by interval.
+    pub fn iter(&self) -> impl Iterator<Item = (IntInterval, &V)> + '_ {
+        self.entries
+            .iter()
+            .map(|(interval, value)| (*interval, value))
+    }
+
+    /// Computes the largest upper bound of each subtree in the entries `lo..hi`, whose root
+    /// is the middle entry.
+    ///
+    /// # Parameters
+    /// - `lo`: The index of the first entry of the subtree.
+    /// - `hi`: One more than the index of the last entry of the subtree.
+    ///
+    /// # Returns
+    /// The largest upper bound in the subtree, or `isize::MIN` if it's empty.
+    fn build(&mut self, lo: usize, hi: usize) -> isize {
+        if lo >= hi {
+            return isize::MIN;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let high = max(
+            self.entries[mid].0.high(),
+            max(self.build(lo, mid), self.build(mid + 1, hi)),
+        );
+        self.max_high[mid] = high;
+        high
+    }
+
+    /// Finds the intervals in the entries `lo..hi` that overlap an interval.
+    ///
+    /// # Parameters
+    /// - `lo`: The index of the first entry of the subtree.
+    /// - `hi`: One more than the index of the last entry of the subtree.
+    /// - `interval`: The interval.
+    /// - `found`: The overlapping intervals found so far.
+    fn search<'a>(
+        &'a self,
+        lo: usize,
+        hi: usize,
+        interval: IntInterval,
+        found: &mut Vec<(IntInterval, &'a V)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        if self.max_high[mid] < interval.low() {
+            return;
+        }
+
+        self.search(lo, mid, interval, found);
+        let (entry, value) = &self.entries[mid];
+        // Everything after this entry starts at or after it, so it can't overlap either.
+        if entry.low() > interval.high() {
+            return;
+        }
+
+        if entry.high() >= interval.low() {
+            found.push((*entry, value));
+        }
+
+        self.search(mid + 1, hi, interval, found);
+    }
+}
+
+impl<V> FromIterator<(IntInterval, V)> for IntervalTree<V> {
+    fn from_iter<I: IntoIterator<Item = (IntInterval, V)>>(iter: I) -> Self {
+        IntervalTree::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod interval_tree_tests {
+    use super::IntervalTree;
+    use crate::interval::IntInterval;
+
+    fn tree() -> IntervalTree<char> {
+        [
+            (IntInterval::new(15, 20), 'a'),
+            (IntInterval::new(10, 30), 'b'),
+            (IntInterval::new(17, 19), 'c'),
+            (IntInterval::new(5, 20), 'd'),
+            (IntInterval::new(12, 15), 'e'),
+            (IntInterval::new(30, 40), 'f'),
+            (IntInterval::new(8, 2), 'g'),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_stab() {
+        let tree = tree();
+        assert_eq!(6, tree.len());
+        assert_eq!(
+            vec!['d', 'b', 'e', 'a'],
+            tree.stab(15)
+                .into_iter()
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                (IntInterval::new(10, 30), &'b'),
+                (IntInterval::new(30, 40), &'f')
+            ],
+            tree.stab(30)
+        );
+        assert!(tree.stab(4).is_empty());
+        assert!(tree.stab(41).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let tree = tree();
+        assert_eq!(
+            vec!['d', 'b', 'e'],
+            tree.overlapping(IntInterval::new(0, 12))
+                .into_iter()
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(6, tree.overlapping(IntInterval::new(0, 100)).len());
+        assert!(tree.overlapping(IntInterval::new(20, 10)).is_empty());
+        assert!(IntervalTree::<()>::new(vec![]).stab(0).is_empty());
+    }
+
+    #[test]
+    fn test_against_scan() {
+        let intervals = (0..50)
+            .map(|i| IntInterval::new((i * 37) % 101, (i * 37) % 101 + (i * 13) % 29))
+            .collect::<Vec<_>>();
+        let tree = intervals
+            .iter()
+            .map(|i| (*i, ()))
+            .collect::<IntervalTree<_>>();
+        for low in -5..140 {
+            let query = IntInterval::new(low, low + 3);
+            let mut expected = intervals
+                .iter()
+                .filter(|i| i.intersect(&query).is_some())
+                .copied()
+                .collect::<Vec<_>>();
+            expected.sort();
+            assert_eq!(
+                expected,
+                tree.overlapping(query)
+                    .into_iter()
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
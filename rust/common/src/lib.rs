@@ -1,4 +1,6 @@
 pub mod constants;
+pub mod cycle;
+pub mod dsu;
 pub mod graphs;
 pub mod intcode;
 pub mod interval;
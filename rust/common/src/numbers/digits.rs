@@ -0,0 +1,294 @@
+use num_traits::{checked_pow, PrimInt};
+
+/// An iterator over the digits of an integer, from most to least significant. It can also be
+/// iterated in reverse, from least to most significant.
+#[derive(Clone, Debug)]
+pub struct Digits<T> {
+    /// The digits that haven't been taken from the back yet.
+    value: u128,
+    /// The place value of the next digit from the front.
+    place: u128,
+    base: u128,
+    /// The number of digits left.
+    remaining: u32,
+    digit: std::marker::PhantomData<T>,
+}
+
+impl<T: PrimInt> Iterator for Digits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let digit = self.value / self.place % self.base;
+        self.place /= self.base;
+        self.remaining -= 1;
+        T::from(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<T: PrimInt> DoubleEndedIterator for Digits<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let digit = self.value % self.base;
+        self.value /= self.base;
+        self.place /= self.base;
+        self.remaining -= 1;
+        T::from(digit)
+    }
+}
+
+impl<T: PrimInt> ExactSizeIterator for Digits<T> {}
+
+/// Gets the digits of an integer, without allocating. A negative integer has the same digits
+/// as its absolute value.
+///
+/// # Parameters
+/// - `n`: The integer.
+/// - `base`: The base.
+///
+/// # Returns
+/// An iterator over the digits, from most to least significant. `0` has a single digit.
+///
+/// # Panics
+/// If the base is less than `2`.
+pub fn digits<T: PrimInt>(n: T, base: T) -> Digits<T> {
+    let remaining = num_digits(n, base);
+    let base = magnitude(base);
+    Digits {
+        value: magnitude(n),
+        place: base.pow(remaining - 1),
+        base,
+        remaining,
+        digit: std::marker::PhantomData,
+    }
+}
+
+/// Combines digits into an integer.
+///
+/// # Parameters
+/// - `digits`: The digits, from most to least significant.
+/// - `base`: The base.
+///
+/// # Returns
+/// The integer, which is `0` if there are no digits.
+///
+/// # Panics
+/// If the integer doesn't fit in `T`.
+pub fn from_digits<T: PrimInt>(digits: impl IntoIterator<Item = T>, base: T) -> T {
+    digits.into_iter().fold(T::zero(), |n, digit| {
+        n.checked_mul(&base)
+            .and_then(|n| n.checked_add(&digit))
+            .expect("integer should fit in the type")
+    })
+}
+
+/// Gets the number of digits in an integer. A negative integer has the same number of digits
+/// as its absolute value.
+///
+/// # Parameters
+/// - `n`: The integer.
+/// - `base`: The base.
+///
+/// # Returns
+/// The number of digits, which is `1` for `0`.
+///
+/// # Panics
+/// If the base is less than `2`.
+pub fn num_digits<T: PrimInt>(n: T, base: T) -> u32 {
+    assert!(base > T::one(), "base should be at least 2");
+    let (n, base) = (magnitude(n), magnitude(base));
+    let log = if base == 10 {
+        n.checked_ilog10()
+    } else {
+        n.checked_ilog(base)
+    };
+    log.map_or(1, |log| log + 1)
+}
+
+/// Splits an integer into its leading digits and its last `k` digits.
+///
+/// # Parameters
+/// - `n`: The integer.
+/// - `k`: The number of digits to split off the end.
+/// - `base`: The base.
+///
+/// # Returns
+/// A tuple where the first element is the integer made of the leading digits (`0` if there
+/// are `k` or fewer digits) and the second element is the integer made of the last `k`
+/// digits. For example, splitting `253000` at `3` gives `(253, 0)`.
+pub fn split_at_digit<T: PrimInt>(n: T, k: u32, base: T) -> (T, T) {
+    match checked_pow(base, k as usize) {
+        Some(place) => (n / place, n % place),
+        None => (T::zero(), n),
+    }
+}
+
+/// Concatenates the digits of two integers, e.g., `12 || 345 = 12345` in base 10.
+///
+/// # Parameters
+/// - `a`: The integer whose digits come first.
+/// - `b`: The integer whose digits come last, which should not be negative.
+/// - `base`: The base.
+///
+/// # Returns
+/// The concatenated integer.
+///
+/// # Panics
+/// If the concatenated integer doesn't fit in `T`.
+pub fn concat<T: PrimInt>(a: T, b: T, base: T) -> T {
+    checked_pow(base, num_digits(b, base) as usize)
+        .and_then(|place| a.checked_mul(&place))
+        .and_then(|a| a.checked_add(&b))
+        .expect("concatenated integer should fit in the type")
+}
+
+/// Checks if the digits of an integer read the same forwards and backwards.
+///
+/// # Parameters
+/// - `n`: The integer.
+/// - `base`: The base.
+///
+/// # Returns
+/// `true` if the integer is a palindrome, `false` otherwise.
+pub fn is_palindrome<T: PrimInt>(n: T, base: T) -> bool {
+    let mut digits = digits(n, base);
+    while let (Some(front), Some(back)) = (digits.next(), digits.next_back()) {
+        if front != back {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Groups the digits of an integer into runs of the same digit.
+///
+/// # Parameters
+/// - `n`: The integer.
+/// - `base`: The base.
+///
+/// # Returns
+/// An iterator over each run's digit and length, from most to least significant. For example,
+/// the runs of `111223` are `(1, 3)`, `(2, 2)` and `(3, 1)`.
+pub fn digit_runs<T: PrimInt>(n: T, base: T) -> impl Iterator<Item = (T, usize)> {
+    let mut digits = digits(n, base).peekable();
+    std::iter::from_fn(move || {
+        let digit = digits.next()?;
+        let mut len = 1;
+        while digits.next_if_eq(&digit).is_some() {
+            len += 1;
+        }
+
+        Some((digit, len))
+    })
+}
+
+/// Gets the absolute value of an integer, which always fits in a `u128`.
+///
+/// # Parameters
+/// - `n`: The integer.
+///
+/// # Returns
+/// The absolute value.
+fn magnitude<T: PrimInt>(n: T) -> u128 {
+    n.to_u128()
+        .or_else(|| n.to_i128().map(i128::unsigned_abs))
+        .expect("integer should fit in 128 bits")
+}
+
+#[cfg(test)]
+mod digits_tests {
+    use super::{
+        concat, digit_runs, digits, from_digits, is_palindrome, num_digits, split_at_digit,
+    };
+
+    #[test]
+    #[allow(clippy::zero_prefixed_literal)]
+    fn test_digits() {
+        assert_eq!(vec![1, 0, 1, 0], digits(1010, 10).collect::<Vec<_>>());
+        assert_eq!(vec![0], digits(0, 10).collect::<Vec<_>>());
+        assert_eq!(
+            vec![9, 9, 2, 9, 9],
+            digits(99299isize, 10).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![5, 2], digits(0052isize, 10).collect::<Vec<_>>());
+        assert_eq!(vec![5, 2], digits(-52i32, 10).collect::<Vec<_>>());
+        assert_eq!(vec![1, 1, 0, 1], digits(13u8, 2).collect::<Vec<_>>());
+        assert_eq!(vec![15, 15], digits(255u8, 16).collect::<Vec<_>>());
+        assert_eq!(
+            vec![8, 0, 8, 5, 7],
+            digits(i64::MIN, 10).rev().take(5).collect::<Vec<_>>()
+        );
+        assert_eq!(39, digits(u128::MAX, 10).len());
+
+        let mut both_ends = digits(12345usize, 10);
+        assert_eq!(Some(1), both_ends.next());
+        assert_eq!(Some(5), both_ends.next_back());
+        assert_eq!(Some(2), both_ends.next());
+        assert_eq!(Some(4), both_ends.next_back());
+        assert_eq!(Some(3), both_ends.next_back());
+        assert_eq!(None, both_ends.next());
+    }
+
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(1010, from_digits([1, 0, 1, 0], 10));
+        assert_eq!(0, from_digits(Vec::<u32>::new(), 10));
+        assert_eq!(13u8, from_digits(digits(13u8, 2), 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_digits_overflow() {
+        from_digits([2u8, 5, 6], 10);
+    }
+
+    #[test]
+    fn test_num_digits() {
+        assert_eq!(1, num_digits(0, 10));
+        assert_eq!(1, num_digits(9, 10));
+        assert_eq!(2, num_digits(10, 10));
+        assert_eq!(4, num_digits(-1000i64, 10));
+        assert_eq!(20, num_digits(u64::MAX, 10));
+        assert_eq!(8, num_digits(255u8, 2));
+    }
+
+    #[test]
+    fn test_split_and_concat() {
+        assert_eq!((253, 0), split_at_digit(253000, 3, 10));
+        assert_eq!((20, 24), split_at_digit(2024, 2, 10));
+        assert_eq!((0, 17), split_at_digit(17u8, 5, 10));
+        assert_eq!(12345, concat(12, 345, 10));
+        assert_eq!(120, concat(12, 0, 10));
+        assert_eq!(0b1011, concat(0b10, 0b11, 2));
+    }
+
+    #[test]
+    fn test_palindrome() {
+        assert!(is_palindrome(0, 10));
+        assert!(is_palindrome(12321, 10));
+        assert!(is_palindrome(1221, 10));
+        assert!(!is_palindrome(1231, 10));
+        assert!(!is_palindrome(10, 10));
+        assert!(is_palindrome(0b1001, 2));
+    }
+
+    #[test]
+    fn test_digit_runs() {
+        assert_eq!(
+            vec![(1, 3), (2, 2), (3, 1)],
+            digit_runs(111223, 10).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![(0, 1)], digit_runs(0, 10).collect::<Vec<_>>());
+    }
+}
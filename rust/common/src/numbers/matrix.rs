@@ -0,0 +1,136 @@
+use num_traits::{One, Zero};
+use std::ops::Mul;
+
+/// An `N` by `N` matrix over any ring `T` (e.g., integers or [`ModInt`](super::ModInt)s). This
+/// is useful for linear recurrences, where the `n`th term can be found by raising the
+/// recurrence's matrix to the `n`th power.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Matrix<T, const N: usize> {
+    rows: [[T; N]; N],
+}
+
+impl<T: Copy + Zero + One, const N: usize> Matrix<T, N> {
+    /// Creates a matrix.
+    ///
+    /// # Parameters
+    /// - `rows`: The rows of the matrix.
+    ///
+    /// # Returns
+    /// The matrix.
+    pub fn new(rows: [[T; N]; N]) -> Self {
+        Self { rows }
+    }
+
+    /// Creates the identity matrix.
+    ///
+    /// # Returns
+    /// The matrix with ones on the diagonal and zeros everywhere else.
+    pub fn identity() -> Self {
+        let mut rows = [[T::zero(); N]; N];
+        for (i, row) in rows.iter_mut().enumerate() {
+            row[i] = T::one();
+        }
+
+        Self { rows }
+    }
+
+    /// Gets the rows of this matrix.
+    ///
+    /// # Returns
+    /// The rows.
+    pub fn rows(&self) -> &[[T; N]; N] {
+        &self.rows
+    }
+
+    /// Multiplies this matrix by a column vector.
+    ///
+    /// # Parameters
+    /// - `vector`: The vector.
+    ///
+    /// # Returns
+    /// The product.
+    pub fn apply(&self, vector: &[T; N]) -> [T; N] {
+        self.rows.map(|row| {
+            row.iter()
+                .zip(vector)
+                .fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+        })
+    }
+
+    /// Raises this matrix to a power, using exponentiation by squaring.
+    ///
+    /// # Parameters
+    /// - `exp`: The exponent.
+    ///
+    /// # Returns
+    /// The power, which is the identity matrix if `exp` is `0`.
+    pub fn pow(&self, exp: u64) -> Self {
+        let mut result = Self::identity();
+        let mut base = *self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+
+            // Squaring past the last set bit isn't needed, and could overflow.
+            exp >>= 1;
+            if exp > 0 {
+                base = base * base;
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: Copy + Zero + One, const N: usize> Mul for Matrix<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut rows = [[T::zero(); N]; N];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..N).fold(T::zero(), |acc, k| acc + self.rows[i][k] * rhs.rows[k][j]);
+            }
+        }
+
+        Self { rows }
+    }
+}
+
+#[cfg(test)]
+mod matrix_tests {
+    use super::Matrix;
+    use crate::numbers::ModInt;
+
+    #[test]
+    fn test_mul() {
+        let a = Matrix::new([[1i64, 2], [3, 4]]);
+        let b = Matrix::new([[0i64, 1], [1, 0]]);
+        assert_eq!(Matrix::new([[2, 1], [4, 3]]), a * b);
+        assert_eq!(a, a * Matrix::identity());
+        assert_eq!([5, 11], a.apply(&[1, 2]));
+    }
+
+    #[test]
+    fn test_pow() {
+        // Fibonacci numbers: [F(n+1), F(n)] = M^n [1, 0].
+        let fib = Matrix::new([[1u64, 1], [1, 0]]);
+        assert_eq!([89, 55], fib.pow(10).apply(&[1, 0]));
+        assert_eq!(Matrix::identity(), fib.pow(0));
+        // M^128 overflows a u64, so the last bit shouldn't square the base again.
+        assert_eq!(
+            [17_167_680_177_565, 10_610_209_857_723],
+            fib.pow(64).apply(&[1, 0])
+        );
+
+        type Mod = ModInt<1_000_000_007>;
+        let fib = Matrix::new([[Mod::from(1), Mod::from(1)], [Mod::from(1), Mod::from(0)]]);
+        // F(10^18) mod 10^9 + 7.
+        assert_eq!(
+            209_783_453,
+            fib.pow(1_000_000_000_000_000_000).rows()[0][1].value()
+        );
+    }
+}
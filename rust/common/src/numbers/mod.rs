@@ -0,0 +1,255 @@
+use num_traits::{Euclid, PrimInt, Signed};
+
+pub mod digits;
+mod matrix;
+mod modular;
+mod primes;
+mod rational;
+pub mod sequence;
+pub use matrix::Matrix;
+pub use modular::{mul_mod, pow_mod, Affine, ModInt};
+pub use primes::{divisors, factorize, is_prime, primes_between, primes_up_to, totient};
+pub use rational::Rational;
+
+/// Computes the GCD of two integers.
+///
+/// # Parameters
+/// - `a`: The first integer.
+/// - `b`: The second integer.
+///
+/// # Returns
+/// The GCD of the two integers, which is never negative. The GCD of `0` and `0` is `0`.
+///
+/// # Panics
+/// If the GCD doesn't fit in `T`, e.g., the GCD of `i64::MIN` and `0`.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::zero() {
+        // The remainder only overflows for `T::MIN % -1`, which is 0.
+        let rem = match a.checked_div(&b) {
+            Some(_) => a % b,
+            None => T::zero(),
+        };
+        (a, b) = (b, rem);
+    }
+
+    if a < T::zero() {
+        T::zero()
+            .checked_sub(&a)
+            .expect("gcd should fit in the type")
+    } else {
+        a
+    }
+}
+
+/// Computes the LCM of two integers.
+///
+/// # Parameters
+/// - `a`: The first integer.
+/// - `b`: The second integer.
+///
+/// # Returns
+/// The LCM of the two integers, which is never negative, or `None` if it doesn't fit in `T`.
+/// The LCM of `0` and any integer is `0`.
+pub fn checked_lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+
+    let lcm = (a / gcd(a, b)).checked_mul(&b)?;
+    if lcm < T::zero() {
+        T::zero().checked_sub(&lcm)
+    } else {
+        Some(lcm)
+    }
+}
+
+/// Computes the LCM of two integers.
+///
+/// # Parameters
+/// - `a`: The first integer.
+/// - `b`: The second integer.
+///
+/// # Returns
+/// The LCM of the two integers, which is never negative.
+///
+/// # Panics
+/// If the LCM doesn't fit in `T`.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm should fit in the type")
+}
+
+/// Computes the LCM of any number of integers.
+///
+/// # Parameters
+/// - `values`: The integers.
+///
+/// # Returns
+/// The LCM of the integers, or `1` if there are none.
+///
+/// # Panics
+/// If the LCM doesn't fit in `T`.
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), lcm)
+}
+
+/// Computes the GCD of two integers, along with the coefficients of Bézout's identity.
+///
+/// # Parameters
+/// - `a`: The first integer.
+/// - `b`: The second integer.
+///
+/// # Returns
+/// A tuple `(g, x, y)`, where `g` is the GCD of the two integers (never negative) and
+/// `a * x + b * y = g`.
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Computes the modular inverse of an integer.
+///
+/// # Parameters
+/// - `a`: The integer.
+/// - `modulus`: The modulus.
+///
+/// # Returns
+/// The integer `x` in `[0, modulus)` such that `a * x ≡ 1 (mod modulus)`, or `None` if `a` and
+/// `modulus` aren't coprime.
+///
+/// # Panics
+/// If the modulus isn't positive.
+pub fn mod_inverse<T: PrimInt + Signed + Euclid>(a: T, modulus: T) -> Option<T> {
+    assert!(modulus > T::zero(), "modulus should be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(&modulus), modulus);
+    (g == T::one()).then(|| x.rem_euclid(&modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` using the Chinese Remainder
+/// Theorem. The moduli don't need to be coprime.
+///
+/// # Parameters
+/// - `congruences`: The `(residue, modulus)` of each congruence.
+///
+/// # Returns
+/// A tuple `(x, m)`, where `x` is the smallest non-negative solution and `m` is the LCM of the
+/// moduli, so that every solution is `x + k * m` for some integer `k`. If the congruences
+/// contradict each other, `None` is returned. No congruences are solved by `(0, 1)`.
+///
+/// # Panics
+/// If any modulus isn't positive, or if the LCM of the moduli doesn't fit in `T`.
+pub fn crt<T: PrimInt + Signed + Euclid>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut x, mut m) = (T::zero(), T::one());
+    for &(residue, modulus) in congruences {
+        assert!(modulus > T::zero(), "modulus should be positive");
+        let residue = residue.rem_euclid(&modulus);
+
+        // Solve x + m * t ≡ residue (mod modulus) for t. With m * p ≡ g (mod modulus), that's
+        // t ≡ (residue - x) / g * p (mod modulus / g).
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != T::zero() {
+            return None;
+        }
+
+        let step = modulus / g;
+        let t = mul_mod((diff / g).rem_euclid(&step), p.rem_euclid(&step), step);
+
+        // Since t < modulus / g, this is less than the LCM.
+        x = x + m * t;
+        m = checked_lcm(m, modulus).expect("lcm of the moduli should fit in the type");
+    }
+
+    Some((x, m))
+}
+
+#[cfg(test)]
+mod numbers_tests {
+    use super::{checked_lcm, crt, extended_gcd, gcd, lcm, lcm_all, mod_inverse};
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(12usize, 18));
+        assert_eq!(6, gcd(-12i64, 18));
+        assert_eq!(6, gcd(12i64, -18));
+        assert_eq!(5, gcd(0i32, -5));
+        assert_eq!(0, gcd(0u64, 0));
+        assert_eq!(1, gcd(i64::MIN, -1));
+        assert_eq!(1 << 100, gcd(1i128 << 100, 3 << 100));
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(36, lcm(12u64, 18));
+        assert_eq!(36, lcm(-12i64, 18));
+        assert_eq!(0, lcm(0i32, 7));
+        assert_eq!(None, checked_lcm(u64::MAX, u64::MAX - 1));
+        assert_eq!(Some(u64::MAX), checked_lcm(u64::MAX, 5));
+        assert_eq!(1, lcm_all(Vec::<i64>::new()));
+        assert_eq!(2520, lcm_all(1..=10u32));
+        assert_eq!(6, lcm_all([2usize, 3]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_lcm_overflow() {
+        lcm_all([i64::MAX, i64::MAX - 1]);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [
+            (240i64, 46),
+            (-240, 46),
+            (46, -240),
+            (0, 7),
+            (7, 0),
+            (17, 17),
+        ] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(gcd(a, b), g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(Some(4), mod_inverse(3i64, 11));
+        assert_eq!(Some(7), mod_inverse(-3i64, 11));
+        assert_eq!(None, mod_inverse(6i64, 9));
+        assert_eq!(Some(0), mod_inverse(5i32, 1));
+        let big = 1_000_000_000_000_000_003i128;
+        let inv = mod_inverse(123_456_789i128, big).unwrap();
+        assert_eq!(1, 123_456_789 * inv % big);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((23, 105)), crt(&[(2i64, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((0, 1)), crt::<i64>(&[]));
+
+        // The moduli don't need to be coprime.
+        assert_eq!(Some((10, 12)), crt(&[(4i64, 6), (2, 4)]));
+        assert_eq!(None, crt(&[(1i64, 6), (2, 4)]));
+        assert_eq!(Some((5, 12)), crt(&[(-7i64, 6), (1, 4)]));
+
+        // Products of the moduli overflow an i64 unless they're reduced first.
+        let (m1, m2) = (1_000_000_007i64, 998_244_353i64 * 3);
+        let (x, m) = crt(&[(5, m1), (10, m2)]).unwrap();
+        assert_eq!(m1 * m2, m);
+        assert_eq!((5, 10), (x % m1, x % m2));
+    }
+}
@@ -0,0 +1,435 @@
+use super::mod_inverse;
+use num_traits::{One, PrimInt, Zero};
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Computes `a * b` modulo `modulus` without overflowing, even if `a * b` doesn't fit in `T`.
+///
+/// # Parameters
+/// - `a`: The first integer, in `[0, modulus)`.
+/// - `b`: The second integer, in `[0, modulus)`.
+/// - `modulus`: The modulus, which must be positive.
+///
+/// # Returns
+/// The product, in `[0, modulus)`.
+pub fn mul_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if let Some(product) = a.checked_mul(&b) {
+        return product % modulus;
+    }
+
+    // Otherwise, multiply by doubling and adding, which never goes above the modulus.
+    let add_mod = |x: T, y: T| {
+        if x >= modulus - y {
+            x - (modulus - y)
+        } else {
+            x + y
+        }
+    };
+    let (mut a, mut b, mut product) = (a, b, T::zero());
+    while b > T::zero() {
+        if b & T::one() == T::one() {
+            product = add_mod(product, a);
+        }
+
+        a = add_mod(a, a);
+        b = b >> 1;
+    }
+
+    product
+}
+
+/// Computes `base` to the power of `exp` modulo `modulus`, using exponentiation by squaring.
+///
+/// # Parameters
+/// - `base`: The base, which may be negative.
+/// - `exp`: The exponent.
+/// - `modulus`: The modulus.
+///
+/// # Returns
+/// The power, in `[0, modulus)`. Note that `0` to the power of `0` is `1` (or `0` if the
+/// modulus is `1`).
+///
+/// # Panics
+/// If the exponent is negative or the modulus isn't positive.
+pub fn pow_mod<T: PrimInt>(base: T, exp: T, modulus: T) -> T {
+    assert!(exp >= T::zero(), "exponent should not be negative");
+    assert!(modulus > T::zero(), "modulus should be positive");
+
+    let base = base % modulus;
+    let mut base = if base < T::zero() {
+        base + modulus
+    } else {
+        base
+    };
+    let mut exp = exp;
+    let mut result = T::one() % modulus;
+    while exp > T::zero() {
+        if exp & T::one() == T::one() {
+            result = mul_mod(result, base, modulus);
+        }
+
+        base = mul_mod(base, base, modulus);
+        exp = exp >> 1;
+    }
+
+    result
+}
+
+/// An integer modulo `M`, where every operation wraps around modulo `M`.
+///
+/// Division multiplies by the modular inverse, so it's only defined when the divisor is
+/// coprime to `M` (e.g., any nonzero value if `M` is prime).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct ModInt<const M: u64> {
+    /// The value, in `[0, M)`.
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    /// Creates a new integer modulo `M`.
+    ///
+    /// # Parameters
+    /// - `value`: The value, which may be negative.
+    ///
+    /// # Returns
+    /// The integer modulo `M`.
+    pub fn new(value: i64) -> Self {
+        const { assert!(M > 0, "modulus should be positive") };
+        Self {
+            value: (value as i128).rem_euclid(M as i128) as u64,
+        }
+    }
+
+    /// Gets the value of this integer.
+    ///
+    /// # Returns
+    /// The value, in `[0, M)`.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Computes this integer to the power of `exp`.
+    ///
+    /// # Parameters
+    /// - `exp`: The exponent.
+    ///
+    /// # Returns
+    /// The power.
+    pub fn pow(&self, exp: u64) -> Self {
+        Self {
+            value: pow_mod(self.value, exp, M),
+        }
+    }
+
+    /// Computes the modular inverse of this integer.
+    ///
+    /// # Returns
+    /// The integer that gives `1` when multiplied by this integer, or `None` if this integer
+    /// isn't coprime to `M`.
+    pub fn inverse(&self) -> Option<Self> {
+        mod_inverse(self.value as i128, M as i128).map(|value| Self {
+            value: value as u64,
+        })
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        const { assert!(M > 0, "modulus should be positive") };
+        Self { value: value % M }
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            value: ((self.value as u128 + rhs.value as u128) % M as u128) as u64,
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            value: (M - self.value) % M,
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            value: (self.value as u128 * rhs.value as u128 % M as u128) as u64,
+        }
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// # Panics
+    /// If `rhs` isn't coprime to `M`.
+    fn div(self, rhs: Self) -> Self {
+        let inverse = rhs
+            .inverse()
+            .expect("divisor should be coprime to the modulus");
+        Mul::mul(self, inverse)
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    fn zero() -> Self {
+        Self::from(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    fn one() -> Self {
+        Self::from(1)
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+/// The affine transformation `x -> a * x + b`, e.g., a linear congruential "shuffle" when
+/// `T` is a [`ModInt`]. Composing affine transformations gives another affine transformation,
+/// so applying one many times can be done with exponentiation by squaring.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Affine<T> {
+    a: T,
+    b: T,
+}
+
+impl<T: Copy + Zero + One> Affine<T> {
+    /// Creates the affine transformation `x -> a * x + b`.
+    ///
+    /// # Parameters
+    /// - `a`: The factor.
+    /// - `b`: The offset.
+    ///
+    /// # Returns
+    /// The affine transformation.
+    pub fn new(a: T, b: T) -> Self {
+        Self { a, b }
+    }
+
+    /// Creates the affine transformation `x -> x`.
+    ///
+    /// # Returns
+    /// The affine transformation.
+    pub fn identity() -> Self {
+        Self::new(T::one(), T::zero())
+    }
+
+    /// Gets the factor `a` of this transformation.
+    ///
+    /// # Returns
+    /// The factor.
+    pub fn a(&self) -> T {
+        self.a
+    }
+
+    /// Gets the offset `b` of this transformation.
+    ///
+    /// # Returns
+    /// The offset.
+    pub fn b(&self) -> T {
+        self.b
+    }
+
+    /// Applies this transformation to a value.
+    ///
+    /// # Parameters
+    /// - `x`: The value.
+    ///
+    /// # Returns
+    /// `a * x + b`.
+    pub fn apply(&self, x: T) -> T {
+        self.a * x + self.b
+    }
+
+    /// Composes this transformation with another one, so that applying the result is the same
+    /// as applying this transformation and then `next`.
+    ///
+    /// # Parameters
+    /// - `next`: The transformation to apply after this one.
+    ///
+    /// # Returns
+    /// The composed transformation.
+    pub fn then(&self, next: &Affine<T>) -> Affine<T> {
+        Affine::new(next.a * self.a, next.a * self.b + next.b)
+    }
+
+    /// Composes this transformation with itself `exp` times, using exponentiation by squaring.
+    ///
+    /// # Parameters
+    /// - `exp`: The number of times to apply this transformation.
+    ///
+    /// # Returns
+    /// The composed transformation, which is the identity if `exp` is `0`.
+    pub fn pow(&self, exp: u64) -> Affine<T> {
+        let mut result = Affine::identity();
+        let mut base = *self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.then(&base);
+            }
+
+            // Only compose the base with itself while there are bits left to use it for.
+            exp >>= 1;
+            if exp > 0 {
+                base = base.then(&base);
+            }
+        }
+
+        result
+    }
+}
+
+impl<const M: u64> Affine<ModInt<M>> {
+    /// Computes the inverse of this transformation, i.e., `y -> (y - b) / a`.
+    ///
+    /// # Returns
+    /// The inverse, or `None` if `a` isn't coprime to `M`.
+    pub fn inverse(&self) -> Option<Self> {
+        let a_inv = self.a.inverse()?;
+        Some(Affine::new(a_inv, -self.b * a_inv))
+    }
+}
+
+#[cfg(test)]
+mod modular_tests {
+    use super::{mul_mod, pow_mod, Affine, ModInt};
+
+    type Mod7 = ModInt<7>;
+    type Mod1e9 = ModInt<1_000_000_007>;
+
+    #[test]
+    fn test_mul_mod() {
+        let m = i64::MAX - 24;
+        assert_eq!(
+            ((m as i128 - 1) * (m as i128 - 2) % m as i128) as i64,
+            mul_mod(m - 1, m - 2, m)
+        );
+        assert_eq!(6, mul_mod(2u8, 3, 7));
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(445, pow_mod(4, 13, 497));
+        assert_eq!(1, pow_mod(0, 0, 5));
+        assert_eq!(0, pow_mod(0, 0, 1));
+        assert_eq!(6, pow_mod(-1i64, 1001, 7));
+        // Fermat's little theorem, with a modulus whose squares overflow a u64.
+        let p = 18_446_744_073_709_551_557u64;
+        assert_eq!(1, pow_mod(123_456_789, p - 1, p));
+    }
+
+    #[test]
+    fn test_mod_int() {
+        let a = Mod7::new(5);
+        let b = Mod7::new(-4);
+        assert_eq!(3, b.value());
+        assert_eq!(Mod7::new(1), a + b);
+        assert_eq!(Mod7::new(2), a - b);
+        assert_eq!(Mod7::new(1), a * b);
+        assert_eq!(Mod7::new(2), -a);
+        assert_eq!(a, a / b * b);
+        assert_eq!(Some(Mod7::new(3)), a.inverse());
+        assert_eq!(None, Mod7::new(0).inverse());
+        assert_eq!(Mod7::new(4), a.pow(2));
+        assert_eq!("5", a.to_string());
+        assert_eq!(Mod7::from(15), Mod7::new(1));
+
+        let mut c = Mod1e9::from(2);
+        c *= Mod1e9::from(500_000_004);
+        assert_eq!(1, c.value());
+        assert_eq!(
+            Mod1e9::new(3_628_800),
+            (1..=10).map(Mod1e9::from).product::<Mod1e9>()
+        );
+        assert_eq!(Mod1e9::new(55), (1..=10).map(Mod1e9::from).sum());
+    }
+
+    #[test]
+    fn test_affine() {
+        let f = Affine::new(Mod1e9::new(3), Mod1e9::new(-2));
+        let g = Affine::new(Mod1e9::new(5), Mod1e9::new(1));
+        let x = Mod1e9::new(10);
+        assert_eq!(Mod1e9::new(28), f.apply(x));
+        assert_eq!(g.apply(f.apply(x)), f.then(&g).apply(x));
+        assert_eq!(x, f.inverse().unwrap().apply(f.apply(x)));
+
+        let mut repeated = x;
+        for _ in 0..1000 {
+            repeated = f.apply(repeated);
+        }
+        assert_eq!(repeated, f.pow(1000).apply(x));
+        assert_eq!(Affine::identity(), f.pow(0));
+
+        // Over plain integers too.
+        assert_eq!(Affine::new(8i64, 7), Affine::new(2i64, 1).pow(3));
+        // 2^62 fits in an i64, but squaring it again wouldn't.
+        assert_eq!(1 << 62, Affine::new(2i64, 0).pow(62).a());
+    }
+}
@@ -0,0 +1,368 @@
+use super::{gcd, pow_mod};
+
+/// The number of integers that the segmented sieve looks at a time.
+const SEGMENT_SIZE: u64 = 1 << 16;
+/// Factors up to this are found by trial division before switching to Pollard's rho.
+const TRIAL_DIVISION_LIMIT: u64 = 1 << 10;
+
+/// Finds every prime up to some limit.
+///
+/// # Parameters
+/// - `limit`: The limit, inclusive.
+///
+/// # Returns
+/// The primes, in increasing order.
+pub fn primes_up_to(limit: u64) -> Vec<u64> {
+    primes_between(0, limit)
+}
+
+/// Finds every prime in a range using a segmented Sieve of Eratosthenes. Only the primes up
+/// to `sqrt(high)` and one segment of the range are kept in memory at a time, so the range
+/// can be far from `0`.
+///
+/// # Parameters
+/// - `low`: The start of the range, inclusive.
+/// - `high`: The end of the range, inclusive.
+///
+/// # Returns
+/// The primes, in increasing order.
+pub fn primes_between(low: u64, high: u64) -> Vec<u64> {
+    let low = low.max(2);
+    if low > high {
+        return vec![];
+    }
+
+    // The primes that can be the smallest factor of a composite number in the range.
+    let sqrt = high.isqrt();
+    let mut is_composite = vec![false; sqrt as usize + 1];
+    let mut base_primes = vec![];
+    for n in 2..=sqrt {
+        if is_composite[n as usize] {
+            continue;
+        }
+
+        base_primes.push(n);
+        for multiple in (n * n..=sqrt).step_by(n as usize) {
+            is_composite[multiple as usize] = true;
+        }
+    }
+
+    let mut primes = vec![];
+    let mut start = low;
+    loop {
+        let end = start.saturating_add(SEGMENT_SIZE - 1).min(high);
+        let mut is_composite = vec![false; (end - start + 1) as usize];
+        for &p in &base_primes {
+            if p * p > end {
+                break;
+            }
+
+            // The first multiple of p in the segment that isn't p itself.
+            let Some(first) = start.div_ceil(p).max(p).checked_mul(p) else {
+                continue;
+            };
+            for multiple in (first..=end).step_by(p as usize) {
+                is_composite[(multiple - start) as usize] = true;
+            }
+        }
+
+        primes.extend(
+            is_composite
+                .iter()
+                .enumerate()
+                .filter(|(_, composite)| !**composite)
+                .map(|(offset, _)| start + offset as u64),
+        );
+
+        if end == high {
+            return primes;
+        }
+
+        start = end + 1;
+    }
+}
+
+/// Checks if an integer is prime, using a Miller-Rabin test with bases that are known to give
+/// the right answer for every `u64`.
+///
+/// # Parameters
+/// - `n`: The integer.
+///
+/// # Returns
+/// `true` if the integer is prime, `false` otherwise.
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+
+    if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+
+    // n - 1 = d * 2^s, where d is odd.
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let n = n as u128;
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a as u128, d as u128, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+
+        (1..s).any(|_| {
+            x = x * x % n;
+            x == n - 1
+        })
+    })
+}
+
+/// Finds the prime factorization of an integer. Small factors are found by trial division,
+/// and the rest with Pollard's rho algorithm.
+///
+/// # Parameters
+/// - `n`: The integer.
+///
+/// # Returns
+/// Each prime factor and its exponent, in increasing order of the prime. The factorization
+/// of `1` is empty.
+///
+/// # Panics
+/// If the integer is `0`.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "n should be positive");
+    let mut factors = vec![];
+    let mut n = n;
+    let mut p = 2;
+    while p <= TRIAL_DIVISION_LIMIT && p * p <= n {
+        let mut exp = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            exp += 1;
+        }
+
+        if exp > 0 {
+            factors.push((p, exp));
+        }
+
+        p += 1;
+    }
+
+    // Everything left has only large prime factors.
+    let mut large = vec![];
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+
+        if is_prime(m) {
+            large.push(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+
+    large.sort_unstable();
+    for p in large {
+        match factors.last_mut() {
+            Some((last, exp)) if *last == p => *exp += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+
+    factors
+}
+
+/// Finds every divisor of an integer.
+///
+/// # Parameters
+/// - `n`: The integer.
+///
+/// # Returns
+/// The positive divisors, in increasing order.
+///
+/// # Panics
+/// If the integer is `0`.
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for (p, exp) in factorize(n) {
+        let count = divisors.len();
+        let mut power = 1;
+        for _ in 0..exp {
+            power *= p;
+            for i in 0..count {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+
+    divisors.sort_unstable();
+    divisors
+}
+
+/// Computes Euler's totient function of an integer.
+///
+/// # Parameters
+/// - `n`: The integer.
+///
+/// # Returns
+/// The number of integers in `[1, n]` that are coprime to `n`.
+///
+/// # Panics
+/// If the integer is `0`.
+pub fn totient(n: u64) -> u64 {
+    factorize(n)
+        .into_iter()
+        .fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+/// Finds a nontrivial factor of a composite integer using Brent's variant of Pollard's rho
+/// algorithm.
+///
+/// # Parameters
+/// - `n`: The integer, which must be composite.
+///
+/// # Returns
+/// A factor of the integer other than `1` and `n`.
+fn pollard_rho(n: u64) -> u64 {
+    /// The number of steps to take between GCDs.
+    const BATCH: u64 = 128;
+
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    // Not every sequence finds a factor, so try another one if it fails.
+    for c in 1.. {
+        let f = |x: u64| ((x as u128 * x as u128 + c as u128) % n as u128) as u64;
+        let (mut x, mut y, mut ys) = (2, 2, 2);
+        let (mut r, mut q, mut g) = (1, 1, 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mul(q, x.abs_diff(y));
+                }
+
+                g = gcd(q, n);
+                k += BATCH;
+            }
+
+            r *= 2;
+        }
+
+        // The batch overshot, so go back through it one step at a time.
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+
+        if g != n {
+            return g;
+        }
+    }
+
+    unreachable!("some sequence should find a factor")
+}
+
+#[cfg(test)]
+mod primes_tests {
+    use super::{divisors, factorize, is_prime, primes_between, primes_up_to, totient};
+
+    #[test]
+    fn test_sieve() {
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29], primes_up_to(30));
+        assert!(primes_up_to(1).is_empty());
+        assert_eq!(78_498, primes_up_to(1_000_000).len());
+        assert_eq!(vec![101, 103, 107, 109, 113], primes_between(100, 113));
+        assert!(primes_between(24, 28).is_empty());
+        assert!(primes_between(10, 5).is_empty());
+    }
+
+    #[test]
+    fn test_segmented_sieve() {
+        let low = 1_000_000_000_000;
+        let primes = primes_between(low, low + 200_000);
+        assert_eq!(
+            (low..=low + 200_000)
+                .filter(|n| is_prime(*n))
+                .collect::<Vec<_>>(),
+            primes
+        );
+        assert_eq!(Some(&1_000_000_000_039), primes.first());
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let small = primes_up_to(10_000);
+        for n in 0..=10_000 {
+            assert_eq!(small.binary_search(&n).is_ok(), is_prime(n), "{n}");
+        }
+
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // A strong pseudoprime to the bases 2 through 37 except for one.
+        assert!(!is_prime(3_825_123_056_546_413_051));
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert!(factorize(1).is_empty());
+        assert_eq!(vec![(2, 2), (3, 2)], factorize(36));
+        assert_eq!(
+            vec![(71, 1), (839, 1), (1471, 1), (6857, 1)],
+            factorize(600_851_475_143)
+        );
+        assert_eq!(
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6_700_417, 1)
+            ],
+            factorize(u64::MAX)
+        );
+        assert_eq!(
+            vec![(998_244_353, 1), (1_000_000_007, 1)],
+            factorize(1_000_000_007 * 998_244_353)
+        );
+        assert_eq!(
+            vec![(4_294_967_291, 2)],
+            factorize(4_294_967_291 * 4_294_967_291)
+        );
+    }
+
+    #[test]
+    fn test_divisors_and_totient() {
+        assert_eq!(vec![1, 2, 4, 7, 14, 28], divisors(28));
+        assert_eq!(vec![1], divisors(1));
+        assert_eq!(6720, divisors(963_761_198_400).len());
+        assert_eq!(12, totient(36));
+        assert_eq!(1, totient(1));
+        assert_eq!(1_000_000_006, totient(1_000_000_007));
+        for n in 1..200u64 {
+            let coprime = (1..=n).filter(|k| super::gcd(*k, n) == 1).count() as u64;
+            assert_eq!(coprime, totient(n), "{n}");
+        }
+    }
+}
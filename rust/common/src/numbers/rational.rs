@@ -0,0 +1,227 @@
+use super::gcd;
+use num_traits::{One, Zero};
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact fraction of two `i128`s, which is always kept in lowest terms with a positive
+/// denominator. Every operation panics instead of overflowing.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rational {
+    numerator: i128,
+    /// The denominator, which is always positive.
+    denominator: i128,
+}
+
+impl Rational {
+    /// Creates a new fraction, reduced to lowest terms.
+    ///
+    /// # Parameters
+    /// - `numerator`: The numerator.
+    /// - `denominator`: The denominator.
+    ///
+    /// # Returns
+    /// The fraction.
+    ///
+    /// # Panics
+    /// If the denominator is `0`.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "denominator should not be 0");
+        let g = gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / g, denominator / g);
+        if denominator < 0 {
+            Self {
+                numerator: numerator
+                    .checked_neg()
+                    .expect("fraction should fit in i128"),
+                denominator: denominator
+                    .checked_neg()
+                    .expect("fraction should fit in i128"),
+            }
+        } else {
+            Self {
+                numerator,
+                denominator,
+            }
+        }
+    }
+
+    /// Gets the numerator of this fraction.
+    ///
+    /// # Returns
+    /// The numerator, in lowest terms.
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    /// Gets the denominator of this fraction.
+    ///
+    /// # Returns
+    /// The denominator, in lowest terms, which is always positive.
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    /// Converts this fraction to an integer, if it is one.
+    ///
+    /// # Returns
+    /// The integer, or `None` if the denominator isn't `1`.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    /// Computes the reciprocal of this fraction.
+    ///
+    /// # Returns
+    /// The fraction with the numerator and denominator swapped.
+    ///
+    /// # Panics
+    /// If this fraction is `0`.
+    pub fn recip(&self) -> Self {
+        Self::new(self.denominator, self.numerator)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // Scaling to the LCM of the denominators keeps the intermediate values small.
+        let g = gcd(self.denominator, rhs.denominator);
+        let (left_scale, right_scale) = (rhs.denominator / g, self.denominator / g);
+        self.numerator
+            .checked_mul(left_scale)
+            .zip(rhs.numerator.checked_mul(right_scale))
+            .and_then(|(a, b)| a.checked_add(b))
+            .zip(self.denominator.checked_mul(left_scale))
+            .map(|(numerator, denominator)| Self::new(numerator, denominator))
+            .expect("sum should fit in i128")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: self
+                .numerator
+                .checked_neg()
+                .expect("negation should fit in i128"),
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // Cancelling across the fractions first keeps the intermediate values small.
+        let g1 = gcd(self.numerator, rhs.denominator);
+        let g2 = gcd(rhs.numerator, self.denominator);
+        (self.numerator / g1)
+            .checked_mul(rhs.numerator / g2)
+            .zip((self.denominator / g2).checked_mul(rhs.denominator / g1))
+            .map(|(numerator, denominator)| Self::new(numerator, denominator))
+            .expect("product should fit in i128")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// # Panics
+    /// If `rhs` is `0`.
+    fn div(self, rhs: Self) -> Self {
+        Mul::mul(self, rhs.recip())
+    }
+}
+
+impl Zero for Rational {
+    fn zero() -> Self {
+        Self::from(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+}
+
+impl One for Rational {
+    fn one() -> Self {
+        Self::from(1)
+    }
+}
+
+#[cfg(test)]
+mod rational_tests {
+    use super::Rational;
+
+    #[test]
+    fn test_new() {
+        let half = Rational::new(2, 4);
+        assert_eq!((1, 2), (half.numerator(), half.denominator()));
+        assert_eq!(Rational::new(-3, 5), Rational::new(3, -5));
+        assert_eq!(Rational::from(0), Rational::new(0, -7));
+        assert_eq!(Some(3), Rational::new(-9, -3).to_integer());
+        assert_eq!(None, half.to_integer());
+        assert_eq!("-3/5", Rational::new(6, -10).to_string());
+        assert_eq!("4", Rational::from(4).to_string());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_zero_denominator() {
+        Rational::new(1, 0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Rational::new(1, 6);
+        let b = Rational::new(3, 4);
+        assert_eq!(Rational::new(11, 12), a + b);
+        assert_eq!(Rational::new(-7, 12), a - b);
+        assert_eq!(Rational::new(1, 8), a * b);
+        assert_eq!(Rational::new(2, 9), a / b);
+        assert_eq!(Rational::new(4, 3), b.recip());
+        assert_eq!(Rational::from(1), a * Rational::from(6));
+
+        // Cancelling first avoids overflowing even though the product of the numerators
+        // doesn't fit.
+        let big = Rational::new(i128::MAX, 3);
+        assert_eq!(Rational::from(i128::MAX), big * Rational::from(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_overflow() {
+        let _ = Rational::from(i128::MAX) + Rational::from(1);
+    }
+}
@@ -0,0 +1,254 @@
+use super::Rational;
+use crate::cycle::{find_cycle, Cycle};
+use num_traits::{PrimInt, Signed};
+
+/// Finds the value of the lowest-degree polynomial through some evenly-spaced samples at an
+/// index, using Newton's forward differences. This is the same as repeatedly taking the
+/// differences of the samples until they're all `0`, then building the difference table
+/// back up, but the index can be arbitrarily far away (or negative).
+///
+/// # Parameters
+/// - `values`: The samples, where the `i`th sample is the value at index `i`.
+/// - `index`: The index to find the value at.
+///
+/// # Returns
+/// The value at the index.
+///
+/// # Panics
+/// If there are no samples, or the value doesn't fit in `T`.
+pub fn extrapolate<T: PrimInt>(values: &[T], index: i64) -> T {
+    assert!(!values.is_empty(), "there should be at least one sample");
+
+    // Turn the samples into the leading entry of each row of the difference table, where
+    // diffs[k] is the kth difference at index 0.
+    let mut diffs = values
+        .iter()
+        .map(|value| value.to_i128().expect("sample should fit in i128"))
+        .collect::<Vec<_>>();
+    for k in 1..diffs.len() {
+        for i in (k..diffs.len()).rev() {
+            diffs[i] -= diffs[i - 1];
+        }
+    }
+
+    // The differences past the degree of the polynomial are all 0, and skipping them keeps
+    // the binomial coefficients from overflowing when the index is large.
+    let degree = diffs.iter().rposition(|diff| *diff != 0).unwrap_or(0);
+    let index = index as i128;
+    let mut binomial = 1i128;
+    let mut value = 0i128;
+    for (k, diff) in diffs[..=degree].iter().enumerate() {
+        if k > 0 {
+            // C(n, k) = C(n, k - 1) * (n - k + 1) / k, which always divides evenly.
+            binomial = binomial
+                .checked_mul(index - k as i128 + 1)
+                .expect("value should fit in i128")
+                / k as i128;
+        }
+
+        value = binomial
+            .checked_mul(*diff)
+            .and_then(|term| value.checked_add(term))
+            .expect("value should fit in i128");
+    }
+
+    T::from(value).expect("value should fit in the type")
+}
+
+/// Finds the value that comes after some evenly-spaced samples of a polynomial.
+///
+/// # Parameters
+/// - `values`: The samples.
+///
+/// # Returns
+/// The next value.
+///
+/// # Panics
+/// If there are no samples, or the value doesn't fit in `T`.
+pub fn next_term<T: PrimInt>(values: &[T]) -> T {
+    extrapolate(values, values.len() as i64)
+}
+
+/// Finds the value that comes before some evenly-spaced samples of a polynomial.
+///
+/// # Parameters
+/// - `values`: The samples.
+///
+/// # Returns
+/// The previous value.
+///
+/// # Panics
+/// If there are no samples, or the value doesn't fit in `T`.
+pub fn previous_term<T: PrimInt>(values: &[T]) -> T {
+    extrapolate(values, -1)
+}
+
+/// Finds the value of the lowest-degree polynomial through some points at an `x`-coordinate,
+/// using Lagrange interpolation. Unlike [`extrapolate`], the points don't need to be evenly
+/// spaced, and the value is computed exactly even if it isn't an integer.
+///
+/// # Parameters
+/// - `points`: The `(x, y)` points.
+/// - `x`: The `x`-coordinate to find the value at.
+///
+/// # Returns
+/// The value at `x`, which is `0` if there are no points.
+///
+/// # Panics
+/// If two points have the same `x`-coordinate, or the value doesn't fit in a [`Rational`].
+pub fn interpolate(points: &[(i64, i64)], x: i64) -> Rational {
+    let mut value = Rational::from(0);
+    for (j, &(xj, yj)) in points.iter().enumerate() {
+        let mut term = Rational::from(yj as i128);
+        for (m, &(xm, _)) in points.iter().enumerate() {
+            if m == j {
+                continue;
+            }
+
+            assert!(xj != xm, "x-coordinates should be distinct");
+            term = term * Rational::new(x as i128 - xm as i128, xj as i128 - xm as i128);
+        }
+
+        value = value + term;
+    }
+
+    value
+}
+
+/// Finds the shortest cycle in the differences of a sampled sequence, e.g., the heights of a
+/// growing tower. After the cycle starts, every `length` values, the sequence goes up by the
+/// same amount, so an arithmetic sequence has a cycle of length `1`. Use
+/// [`extrapolate_cycle`] to find values past the samples, or
+/// [`find_cycle`] if the values themselves repeat.
+///
+/// # Parameters
+/// - `values`: The samples.
+///
+/// # Returns
+/// The cycle with the shortest length (and, for that length, the earliest start), or `None`
+/// if the differences don't repeat.
+pub fn find_arithmetic_cycle<T: PrimInt + Signed>(values: &[T]) -> Option<Cycle> {
+    let diffs = values
+        .windows(2)
+        .map(|window| window[1] - window[0])
+        .collect::<Vec<_>>();
+    find_cycle(&diffs)
+}
+
+/// Finds the value of a sampled sequence at an index, which may be past the samples, using a
+/// cycle from [`find_arithmetic_cycle`].
+///
+/// # Parameters
+/// - `values`: The samples that the cycle was found in. Only the samples up to the end of the
+///   first repetition of the cycle are needed.
+/// - `cycle`: The cycle.
+/// - `index`: The index.
+///
+/// # Returns
+/// The value at the index.
+///
+/// # Panics
+/// If there aren't enough samples to cover the first repetition of the cycle, or the value
+/// doesn't fit in `T`.
+pub fn extrapolate_cycle<T: PrimInt + Signed>(values: &[T], cycle: &Cycle, index: usize) -> T {
+    if let Some(value) = values.get(index) {
+        return *value;
+    }
+
+    let step = values[cycle.start() + cycle.length()] - values[cycle.start()];
+    let repetitions = (index - cycle.start()) / cycle.length();
+    T::from(repetitions)
+        .and_then(|repetitions| step.checked_mul(&repetitions))
+        .and_then(|offset| values[cycle.equivalent_index(index)].checked_add(&offset))
+        .expect("value should fit in the type")
+}
+
+#[cfg(test)]
+mod sequence_tests {
+    use super::{
+        extrapolate, extrapolate_cycle, find_arithmetic_cycle, interpolate, next_term,
+        previous_term,
+    };
+    use crate::numbers::Rational;
+
+    #[test]
+    fn test_next_and_previous_term() {
+        assert_eq!(18, next_term(&[0, 3, 6, 9, 12, 15]));
+        assert_eq!(28, next_term(&[1, 3, 6, 10, 15, 21]));
+        assert_eq!(68, next_term(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(-3, previous_term(&[0, 3, 6, 9, 12, 15]));
+        assert_eq!(0, previous_term(&[1, 3, 6, 10, 15, 21]));
+        assert_eq!(5, previous_term(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(7u8, next_term(&[7u8]));
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // n^2 + 1 at n = 0, 1, 2.
+        let squares = [1i64, 2, 5];
+        assert_eq!(
+            1_000_000_000_000_000_001,
+            extrapolate(&squares, 1_000_000_000)
+        );
+        assert_eq!(10, extrapolate(&squares, -3));
+        assert_eq!(5, extrapolate(&squares, 2));
+
+        // Extra samples on the same polynomial don't change anything, even far away.
+        let cubes = (0..10i128).map(|n| n * n * n).collect::<Vec<_>>();
+        assert_eq!(
+            1_000_000_000_000_000_000_000_000,
+            extrapolate(&cubes, 100_000_000)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extrapolate_empty() {
+        extrapolate::<i32>(&[], 0);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // y = x^2 - 2x + 3.
+        let points = [(1, 2), (4, 11), (-2, 11)];
+        assert_eq!(Some(6), interpolate(&points, 3).to_integer());
+        assert_eq!(
+            Some(9_999_800_003),
+            interpolate(&points, 100_000).to_integer()
+        );
+        // y = x / 2.
+        assert_eq!(Rational::new(3, 2), interpolate(&[(0, 0), (2, 1)], 3));
+        assert_eq!(Rational::from(0), interpolate(&[], 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_interpolate_duplicate_x() {
+        interpolate(&[(1, 2), (1, 3)], 0);
+    }
+
+    #[test]
+    fn test_find_arithmetic_cycle() {
+        let values = [5i64, 7, 9, 11, 13];
+        let cycle = find_arithmetic_cycle(&values).unwrap();
+        assert_eq!((0, 1), (cycle.start(), cycle.length()));
+        assert_eq!(
+            2_000_000_000_005,
+            extrapolate_cycle(&values, &cycle, 1_000_000_000_000)
+        );
+
+        // Goes up by 1, then 3, then 2, forever, after a few irregular values.
+        let mut values = vec![0i64, 10, 4];
+        for i in 0..12 {
+            values.push(values.last().unwrap() + [1, 3, 2][i % 3]);
+        }
+
+        let cycle = find_arithmetic_cycle(&values).unwrap();
+        assert_eq!((2, 3), (cycle.start(), cycle.length()));
+        for (index, value) in values.iter().enumerate() {
+            assert_eq!(*value, extrapolate_cycle(&values[..6], &cycle, index));
+        }
+
+        assert_eq!(None, find_arithmetic_cycle(&[1i64, 2, 4, 8, 16]));
+    }
+}